@@ -0,0 +1,23 @@
+extern crate cursive;
+
+use cursive::Cursive;
+use cursive::layout::Registry;
+use cursive::views::{Dialog, EditView};
+
+fn main() {
+    let mut siv = Cursive::new();
+
+    // Callbacks are bound by name, and referred to from the layout file.
+    let registry = Registry::new().callback("login", |s| {
+        let name = s.call_on_id("username", |view: &mut EditView| {
+                view.get_content()
+            })
+            .unwrap();
+        s.add_layer(Dialog::info(format!("Hello {}!", name)));
+    });
+
+    // The layout can be edited without recompiling the application.
+    siv.add_layer(registry.load_file("assets/login.toml").unwrap());
+
+    siv.run();
+}
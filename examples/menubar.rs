@@ -1,7 +1,7 @@
 extern crate cursive;
 
 use cursive::Cursive;
-use cursive::event::Key;
+use cursive::event::{Event, Key};
 use cursive::menu::MenuTree;
 use cursive::traits::*;
 use cursive::views::Dialog;
@@ -17,8 +17,9 @@ fn main() {
 
     // The menubar is a list of (label, menu tree) pairs.
     siv.menubar()
-        // We add a new "File" tree
-        .add_subtree("File",
+        // We add a new "File" tree.
+        // The `&` makes <Alt-F> open it directly.
+        .add_subtree("&File",
              MenuTree::new()
                  // Trees are made of leaves, with are directly actionable...
                  .leaf("New", move |s| {
@@ -52,8 +53,9 @@ fn main() {
                      }
                  })
                  .delimiter()
-                 .leaf("Quit", |s| s.quit()))
-        .add_subtree("Help",
+                 // Leaves can also have a shortcut, usable from anywhere.
+                 .shortcut_leaf("Quit", Event::CtrlChar('q'), |s| s.quit()))
+        .add_subtree("&Help",
              MenuTree::new()
                  .subtree("Help",
                           MenuTree::new()
@@ -21,8 +21,12 @@ fn main() {
         theme.shadow = !theme.shadow;
         theme.borders = match theme.borders {
             BorderStyle::Simple => BorderStyle::Outset,
-            BorderStyle::Outset => BorderStyle::None,
-            BorderStyle::None => BorderStyle::Simple,
+            BorderStyle::Outset => BorderStyle::Double,
+            BorderStyle::Double => BorderStyle::Rounded,
+            BorderStyle::Rounded => BorderStyle::Heavy,
+            BorderStyle::Heavy => BorderStyle::Ascii,
+            BorderStyle::Ascii => BorderStyle::None,
+            _ => BorderStyle::Simple,
         };
 
         s.set_theme(theme);
@@ -36,7 +36,8 @@ impl backend::Backend for Concrete {
 
     fn with_effect<F: FnOnce()>(&self, effect: Effect, f: F) {
         match effect {
-            Effect::Simple => f(),
+            // BLT has no underline support.
+            Effect::Simple | Effect::Underline | Effect::Bold => f(),
             // TODO: how to do this correctly?`
             //       BLT itself doesn't do this kind of thing,
             //       we'd need the colours in our position,
@@ -55,6 +56,10 @@ impl backend::Backend for Concrete {
         true
     }
 
+    fn color_count(&self) -> u32 {
+        1 << 24
+    }
+
     fn screen_size(&self) -> (usize, usize) {
         let Size { width, height } = terminal::state::size();
         (width as usize, height as usize)
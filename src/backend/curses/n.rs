@@ -2,17 +2,35 @@ extern crate ncurses;
 
 use self::super::find_closest;
 use backend;
-use event::{Event, Key};
+use event::{Event, Key, MouseButton, MouseEvent};
 use std::cell::{RefCell, Cell};
+use std::cmp;
 use std::collections::HashMap;
+use std::ffi::CString;
+use std::io::{self, Write};
+use std::os::raw::{c_char, c_int};
 use theme::{Color, ColorPair, Effect};
 use utf8;
+use vec::Vec2;
 
 pub struct Concrete {
     current_style: Cell<ColorPair>,
     pairs: RefCell<HashMap<ColorPair, i16>>,
+
+    // ncurses doesn't tell which button is held during a drag,
+    // so we remember the last one pressed.
+    last_mouse_button: Option<MouseButton>,
+    mouse_capture: bool,
+}
+
+// Not exposed by the ncurses crate.
+extern "C" {
+    fn define_key(definition: *const c_char, keycode: c_int) -> c_int;
 }
 
+// Key code we ask ncurses to return for the Menu key.
+const KEY_MENU: i32 = 0o1777;
+
 impl Concrete {
     /// Save a new color pair.
     fn insert_color(&self, pairs: &mut HashMap<ColorPair, i16>,
@@ -59,6 +77,67 @@ impl Concrete {
         let style = ncurses::COLOR_PAIR(i);
         ncurses::attron(style);
     }
+
+    fn parse_mouse_event(&mut self) -> Event {
+        let mut mevent = ncurses::MEVENT {
+            id: 0,
+            x: 0,
+            y: 0,
+            z: 0,
+            bstate: 0,
+        };
+        if ncurses::getmouse(&mut mevent as *mut ncurses::MEVENT) ==
+           ncurses::ERR {
+            return Event::Unknown(vec![]);
+        }
+
+        let bstate = mevent.bstate;
+        let has = |mask: i32| bstate & mask as ncurses::mmask_t != 0;
+
+        let event = if has(ncurses::BUTTON4_PRESSED) {
+            MouseEvent::WheelUp
+        } else if has(ncurses::BUTTON5_PRESSED) {
+            MouseEvent::WheelDown
+        } else if let Some(button) = find_button(bstate,
+                                                 ncurses::BUTTON1_PRESSED,
+                                                 ncurses::BUTTON2_PRESSED,
+                                                 ncurses::BUTTON3_PRESSED) {
+            self.last_mouse_button = Some(button);
+            MouseEvent::Press(button)
+        } else if let Some(button) = find_button(bstate,
+                                                 ncurses::BUTTON1_RELEASED,
+                                                 ncurses::BUTTON2_RELEASED,
+                                                 ncurses::BUTTON3_RELEASED) {
+            self.last_mouse_button = None;
+            MouseEvent::Release(button)
+        } else if has(ncurses::REPORT_MOUSE_POSITION) {
+            match self.last_mouse_button {
+                Some(button) => MouseEvent::Hold(button),
+                None => return Event::Refresh,
+            }
+        } else {
+            return Event::Unknown(vec![]);
+        };
+
+        Event::Mouse {
+            position: Vec2::new(mevent.x as usize, mevent.y as usize),
+            event: event,
+        }
+    }
+}
+
+/// Returns the button matching one of the given masks, if any.
+fn find_button(bstate: ncurses::mmask_t, left: i32, middle: i32, right: i32)
+               -> Option<MouseButton> {
+    if bstate & left as ncurses::mmask_t != 0 {
+        Some(MouseButton::Left)
+    } else if bstate & middle as ncurses::mmask_t != 0 {
+        Some(MouseButton::Middle)
+    } else if bstate & right as ncurses::mmask_t != 0 {
+        Some(MouseButton::Right)
+    } else {
+        None
+    }
 }
 
 impl backend::Backend for Concrete {
@@ -76,10 +155,42 @@ impl backend::Backend for Concrete {
         ncurses::use_default_colors();
         ncurses::curs_set(ncurses::CURSOR_VISIBILITY::CURSOR_INVISIBLE);
 
+        // xterm and the Linux console send this for the Menu key,
+        // but terminfo has no entry for it.
+        let menu = CString::new("\x1B[29~").unwrap();
+        unsafe {
+            define_key(menu.as_ptr(), KEY_MENU);
+        }
+
         Concrete {
             current_style: Cell::new(ColorPair::from_256colors(0, 0)),
             pairs: RefCell::new(HashMap::new()),
+            last_mouse_button: None,
+            mouse_capture: false,
+        }
+    }
+
+    fn set_mouse_capture(&mut self, enabled: bool) {
+        if enabled == self.mouse_capture {
+            return;
+        }
+        self.mouse_capture = enabled;
+        self.last_mouse_button = None;
+
+        if enabled {
+            // Report every mouse event, without waiting to detect clicks.
+            let mask = ncurses::ALL_MOUSE_EVENTS |
+                       ncurses::REPORT_MOUSE_POSITION;
+            ncurses::mousemask(mask as ncurses::mmask_t, None);
+            ncurses::mouseinterval(0);
+            // Ask the terminal to also report movements while a button
+            // is held.
+            print!("\x1B[?1002h");
+        } else {
+            ncurses::mousemask(0, None);
+            print!("\x1B[?1002l");
         }
+        io::stdout().flush().unwrap();
     }
 
     fn screen_size(&self) -> (usize, usize) {
@@ -93,7 +204,16 @@ impl backend::Backend for Concrete {
         ncurses::has_colors()
     }
 
+    fn color_count(&self) -> u32 {
+        if ncurses::has_colors() {
+            cmp::min(ncurses::COLORS(), 256) as u32
+        } else {
+            0
+        }
+    }
+
     fn finish(&mut self) {
+        self.set_mouse_capture(false);
         ncurses::endwin();
     }
 
@@ -114,6 +234,8 @@ impl backend::Backend for Concrete {
     fn with_effect<F: FnOnce()>(&self, effect: Effect, f: F) {
         let style = match effect {
             Effect::Reverse => ncurses::A_REVERSE(),
+            Effect::Underline => ncurses::A_UNDERLINE(),
+            Effect::Bold => ncurses::A_BOLD(),
             Effect::Simple => ncurses::A_NORMAL(),
         };
         ncurses::attron(style);
@@ -131,6 +253,12 @@ impl backend::Backend for Concrete {
         ncurses::clear();
     }
 
+    fn reset_colors(&self) {
+        // Pairs will be re-defined as needed, starting from scratch.
+        self.pairs.borrow_mut().clear();
+        self.set_colors(self.current_style.get());
+    }
+
     fn refresh(&mut self) {
         ncurses::refresh();
     }
@@ -147,6 +275,8 @@ impl backend::Backend for Concrete {
             Event::Char(utf8::read_char(ch as u8,
                                         || Some(ncurses::getch() as u8))
                                 .unwrap())
+        } else if ch == ncurses::KEY_MOUSE {
+            self.parse_mouse_event()
         } else {
             parse_ncurses_char(ch)
         }
@@ -183,6 +313,8 @@ fn parse_ncurses_char(ch: i32) -> Event {
 
         410 => Event::WindowResize,
 
+        KEY_MENU => Event::Key(Key::Menu),
+
         // Values 512 and above are probably extensions
         // Those keys don't seem to be documented...
         520 => Event::Alt(Key::Del),
@@ -4,6 +4,7 @@ use self::super::find_closest;
 use backend;
 use event::{Event, Key};
 use std::cell::{RefCell, Cell};
+use std::cmp;
 use std::collections::HashMap;
 use theme::{Color, ColorPair, Effect};
 use utf8;
@@ -91,6 +92,14 @@ impl backend::Backend for Concrete {
         pancurses::has_colors()
     }
 
+    fn color_count(&self) -> u32 {
+        if pancurses::has_colors() {
+            cmp::min(pancurses::COLORS(), 256) as u32
+        } else {
+            0
+        }
+    }
+
     fn finish(&mut self) {
         pancurses::endwin();
     }
@@ -112,6 +121,8 @@ impl backend::Backend for Concrete {
     fn with_effect<F: FnOnce()>(&self, effect: Effect, f: F) {
         let style = match effect {
             Effect::Reverse => pancurses::Attribute::Reverse,
+            Effect::Underline => pancurses::Attribute::Underline,
+            Effect::Bold => pancurses::Attribute::Bold,
             Effect::Simple => pancurses::Attribute::Normal,
         };
         self.window.attron(style);
@@ -128,6 +139,12 @@ impl backend::Backend for Concrete {
         self.window.clear();
     }
 
+    fn reset_colors(&self) {
+        // Pairs will be re-defined as needed, starting from scratch.
+        self.pairs.borrow_mut().clear();
+        self.set_colors(self.current_style.get());
+    }
+
     fn refresh(&mut self) {
         self.window.refresh();
     }
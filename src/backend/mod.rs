@@ -24,6 +24,11 @@ pub trait Backend {
     fn refresh(&mut self);
 
     fn has_colors(&self) -> bool;
+
+    /// Number of colors the terminal can actually display.
+    ///
+    /// `0` means only effects (reverse, bold, ...) are available.
+    fn color_count(&self) -> u32;
     fn screen_size(&self) -> (usize, usize);
 
     /// Main input method
@@ -33,6 +38,16 @@ pub trait Backend {
     fn print_at(&self, (usize, usize), &str);
     fn clear(&self, color: theme::Color);
 
+    /// Forgets any cached color, after the theme changed.
+    ///
+    /// Backends printing colors directly have nothing to do.
+    fn reset_colors(&self) {}
+
+    /// Starts or stops reporting mouse events.
+    ///
+    /// Backends without mouse support have nothing to do.
+    fn set_mouse_capture(&mut self, _enabled: bool) {}
+
     fn set_refresh_rate(&mut self, fps: u32);
     // TODO: unify those into a single method?
     fn with_color<F: FnOnce()>(&self, colors: theme::ColorPair, f: F);
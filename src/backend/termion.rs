@@ -5,25 +5,32 @@ extern crate chan_signal;
 use self::termion::color as tcolor;
 use self::termion::event::Event as TEvent;
 use self::termion::event::Key as TKey;
+use self::termion::event::MouseButton as TMouseButton;
+use self::termion::event::MouseEvent as TMouseEvent;
 use self::termion::input::TermRead;
-use self::termion::raw::IntoRawMode;
+use self::termion::raw::{IntoRawMode, RawTerminal};
 use self::termion::screen::AlternateScreen;
 use self::termion::style as tstyle;
 use backend;
 use chan;
-use event::{Event, Key};
+use event::{Event, Key, MouseButton, MouseEvent};
 use std::cell::Cell;
+use std::env;
 use std::io::Write;
 use std::thread;
 
 use theme;
+use vec::Vec2;
 
 pub struct Concrete {
-    terminal: AlternateScreen<termion::raw::RawTerminal<::std::io::Stdout>>,
+    terminal: AlternateScreen<RawTerminal<::std::io::Stdout>>,
     current_style: Cell<theme::ColorPair>,
     input: chan::Receiver<Event>,
     resize: chan::Receiver<chan_signal::Signal>,
     timeout: Option<u32>,
+    mouse_capture: bool,
+    // Detected once, when the backend starts.
+    color_count: u32,
 }
 
 trait Effectable {
@@ -36,6 +43,8 @@ impl Effectable for theme::Effect {
         match *self {
             theme::Effect::Simple => (),
             theme::Effect::Reverse => print!("{}", tstyle::Invert),
+            theme::Effect::Underline => print!("{}", tstyle::Underline),
+            theme::Effect::Bold => print!("{}", tstyle::Bold),
         }
     }
 
@@ -43,6 +52,9 @@ impl Effectable for theme::Effect {
         match *self {
             theme::Effect::Simple => (),
             theme::Effect::Reverse => print!("{}", tstyle::NoInvert),
+            theme::Effect::Underline => print!("{}", tstyle::NoUnderline),
+            // `NoBold` is not widely supported: reset the intensity instead.
+            theme::Effect::Bold => print!("{}[22m", 27 as char),
         }
     }
 }
@@ -65,11 +77,23 @@ impl backend::Backend for Concrete {
                                                  .unwrap());
         let (sender, receiver) = chan::async();
 
-        thread::spawn(move || for key in ::std::io::stdin().events() {
-                          if let Ok(key) = key {
-                              sender.send(map_key(key))
-                          }
-                      });
+        thread::spawn(move || {
+            // Termion doesn't tell which button is held or released,
+            // so we remember the last one pressed.
+            let mut last_button = None;
+            for key in ::std::io::stdin().events() {
+                match key {
+                    Ok(TEvent::Mouse(event)) => {
+                        if let Some(event) = map_mouse(event,
+                                                       &mut last_button) {
+                            sender.send(event)
+                        }
+                    }
+                    Ok(key) => sender.send(map_key(key)),
+                    Err(_) => (),
+                }
+            }
+        });
 
         let backend = Concrete {
             terminal: terminal,
@@ -77,12 +101,32 @@ impl backend::Backend for Concrete {
             input: receiver,
             resize: resize,
             timeout: None,
+            mouse_capture: false,
+            color_count: detect_color_count(),
         };
 
         backend
     }
 
+    fn set_mouse_capture(&mut self, enabled: bool) {
+        if enabled == self.mouse_capture {
+            return;
+        }
+        self.mouse_capture = enabled;
+
+        // Same sequences as termion's `MouseTerminal`, which can't be
+        // turned on and off.
+        let sequence = if enabled {
+            "\x1B[?1000h\x1B[?1002h\x1B[?1015h\x1B[?1006h"
+        } else {
+            "\x1B[?1006l\x1B[?1015l\x1B[?1002l\x1B[?1000l"
+        };
+        self.terminal.write_all(sequence.as_bytes()).unwrap();
+        self.terminal.flush().unwrap();
+    }
+
     fn finish(&mut self) {
+        self.set_mouse_capture(false);
         print!("{}{}", termion::cursor::Show, termion::cursor::Goto(1, 1));
         print!("{}[49m{}[39m{}",
                27 as char,
@@ -113,8 +157,11 @@ impl backend::Backend for Concrete {
     }
 
     fn has_colors(&self) -> bool {
-        // TODO: color support detection?
-        true
+        self.color_count() > 0
+    }
+
+    fn color_count(&self) -> u32 {
+        self.color_count
     }
 
     fn screen_size(&self) -> (usize, usize) {
@@ -141,7 +188,11 @@ impl backend::Backend for Concrete {
     }
 
     fn set_refresh_rate(&mut self, fps: u32) {
-        self.timeout = Some(1000 / fps as u32);
+        self.timeout = if fps == 0 {
+            None
+        } else {
+            Some(1000 / fps as u32)
+        };
     }
 
     fn poll_event(&mut self) -> Event {
@@ -164,8 +215,34 @@ impl backend::Backend for Concrete {
     }
 }
 
+// termion has no terminfo support: guess from the environment.
+fn detect_color_count() -> u32 {
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return 1 << 24;
+    }
+
+    let term = env::var("TERM").unwrap_or_default();
+    if term.contains("256color") {
+        256
+    } else if term == "linux" {
+        // The linux console only has 8 background colors.
+        8
+    } else if term.starts_with("xterm") || term.starts_with("screen") {
+        16
+    } else if term.is_empty() || term == "dumb" || term.starts_with("vt") {
+        0
+    } else {
+        256
+    }
+}
+
 fn map_key(event: TEvent) -> Event {
     match event {
+        // Termion doesn't know the Menu key.
+        TEvent::Unsupported(ref bytes) if bytes == b"\x1B[29~" => {
+            Event::Key(Key::Menu)
+        }
         TEvent::Unsupported(bytes) => Event::Unknown(bytes),
         TEvent::Key(TKey::Esc) => Event::Key(Key::Esc),
         TEvent::Key(TKey::Backspace) => Event::Key(Key::Backspace),
@@ -192,6 +269,42 @@ fn map_key(event: TEvent) -> Event {
 
 }
 
+fn map_mouse(event: TMouseEvent, last_button: &mut Option<MouseButton>)
+             -> Option<Event> {
+    let (event, x, y) = match event {
+        TMouseEvent::Press(TMouseButton::WheelUp, x, y) => {
+            (MouseEvent::WheelUp, x, y)
+        }
+        TMouseEvent::Press(TMouseButton::WheelDown, x, y) => {
+            (MouseEvent::WheelDown, x, y)
+        }
+        TMouseEvent::Press(button, x, y) => {
+            let button = match button {
+                TMouseButton::Left => MouseButton::Left,
+                TMouseButton::Middle => MouseButton::Middle,
+                _ => MouseButton::Right,
+            };
+            *last_button = Some(button);
+            (MouseEvent::Press(button), x, y)
+        }
+        TMouseEvent::Release(x, y) => match last_button.take() {
+            Some(button) => (MouseEvent::Release(button), x, y),
+            None => return None,
+        },
+        TMouseEvent::Hold(x, y) => match *last_button {
+            Some(button) => (MouseEvent::Hold(button), x, y),
+            None => return None,
+        },
+    };
+
+    // Termion coordinates are one-based.
+    let position = Vec2::new(x as usize, y as usize).saturating_sub((1, 1));
+    Some(Event::Mouse {
+             position: position,
+             event: event,
+         })
+}
+
 fn with_color<F, R>(clr: &theme::Color, f: F) -> R
     where F: FnOnce(&tcolor::Color) -> R
 {
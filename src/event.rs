@@ -15,8 +15,10 @@
 
 
 use Cursive;
+use std::fmt;
 use std::ops::Deref;
 use std::rc::Rc;
+use vec::Vec2;
 
 /// Callback is a function that can be triggered by an event.
 /// It has a mutable access to the cursive root.
@@ -88,6 +90,28 @@ impl EventResult {
         }
     }
 
+    /// Combines two results.
+    ///
+    /// The result is consumed if any of them is,
+    /// and runs both callbacks in order.
+    pub fn and(self, other: Self) -> Self {
+        match (self, other) {
+            (EventResult::Ignored, result) |
+            (result, EventResult::Ignored) => result,
+            (EventResult::Consumed(None), EventResult::Consumed(cb)) |
+            (EventResult::Consumed(cb), EventResult::Consumed(None)) => {
+                EventResult::Consumed(cb)
+            }
+            (EventResult::Consumed(Some(cb1)),
+             EventResult::Consumed(Some(cb2))) => {
+                EventResult::with_cb(move |s| {
+                    cb1(s);
+                    cb2(s);
+                })
+            }
+        }
+    }
+
     /// Returns `self` if it is not `Event::Ignored`, otherwise returns `f()`.
     pub fn or_else<F>(self, f: F) -> Self
     where
@@ -140,6 +164,11 @@ pub enum Key {
     /// The 5 in the center of the keypad, when numlock is disabled.
     NumpadCenter,
 
+    /// The Menu key, usually next to the right Ctrl key.
+    ///
+    /// Only the ncurses and termion backends report it.
+    Menu,
+
     /// F0 key
     F0,
     /// F1 key
@@ -196,6 +225,92 @@ impl Key {
     }
 }
 
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Key::Enter => "Enter",
+            Key::Tab => "Tab",
+            Key::Backspace => "Backspace",
+            Key::Esc => "Esc",
+            Key::Left => "Left",
+            Key::Right => "Right",
+            Key::Up => "Up",
+            Key::Down => "Down",
+            Key::Ins => "Ins",
+            Key::Del => "Del",
+            Key::Home => "Home",
+            Key::End => "End",
+            Key::PageUp => "PageUp",
+            Key::PageDown => "PageDown",
+            Key::PauseBreak => "PauseBreak",
+            Key::NumpadCenter => "NumpadCenter",
+            Key::Menu => "Menu",
+            Key::F0 => "F0",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+        };
+        f.write_str(name)
+    }
+}
+
+/// One of the buttons present on the mouse.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum MouseButton {
+    /// The left button, used for main actions.
+    Left,
+    /// Middle button, probably the wheel. Often pastes text in X11.
+    Middle,
+    /// The right button, for special actions.
+    Right,
+}
+
+/// Represents a possible event sent by the mouse.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub enum MouseEvent {
+    /// A button was pressed.
+    Press(MouseButton),
+    /// A button was released.
+    Release(MouseButton),
+    /// The mouse moved while a button was held down.
+    Hold(MouseButton),
+    /// The wheel was moved up.
+    WheelUp,
+    /// The wheel was moved down.
+    WheelDown,
+}
+
+impl MouseEvent {
+    /// Returns `true` if this event goes to the view under the mouse.
+    ///
+    /// `Hold` and `Release` events go to the focused view instead, so a
+    /// drag keeps going to the view where it started.
+    pub fn is_positional(&self) -> bool {
+        match *self {
+            MouseEvent::Hold(_) |
+            MouseEvent::Release(_) => false,
+            _ => true,
+        }
+    }
+
+    /// Returns `true` if the view under the mouse should take the focus.
+    pub fn grabs_focus(&self) -> bool {
+        match *self {
+            MouseEvent::Press(_) => true,
+            _ => false,
+        }
+    }
+}
+
 /// Represents an event as seen by the application.
 #[derive(PartialEq, Eq, Clone, Hash, Debug)]
 pub enum Event {
@@ -205,6 +320,20 @@ pub enum Event {
     /// Event fired regularly when a auto-refresh is set.
     Refresh,
 
+    /// Sent to the focused view when the focus moves away from it.
+    ///
+    /// This includes when another layer is shown above its own.
+    /// Containers forward it to their focused child, so the whole
+    /// focus chain is notified.
+    FocusLost,
+    /// Sent to a view when it receives the focus.
+    ///
+    /// Containers forward it to their focused child, so the whole
+    /// focus chain is notified.
+    ///
+    /// Views don't receive it when their layer is first shown.
+    FocusGained,
+
     /// A character was entered (includes numbers, punctuation, ...).
     Char(char),
     /// A character was entered with the Ctrl key pressed.
@@ -227,6 +356,16 @@ pub enum Event {
     /// A non-character key was pressed with the Ctrl and Alt keys pressed.
     CtrlAlt(Key),
 
+    /// A mouse event was sent.
+    ///
+    /// Only the ncurses and termion backends report mouse events.
+    Mouse {
+        /// Absolute position of the mouse on the screen.
+        position: Vec2,
+        /// What the mouse did.
+        event: MouseEvent,
+    },
+
     /// An unknown event was received.
     Unknown(Vec<u8>),
 
@@ -235,6 +374,34 @@ pub enum Event {
     Exit,
 }
 
+/// Shows the key combination, like `Ctrl+P` or `Shift+Tab`.
+///
+/// This is the format used to display shortcuts in menus.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Event::WindowResize => write!(f, "WindowResize"),
+            Event::Refresh => write!(f, "Refresh"),
+            Event::FocusLost => write!(f, "FocusLost"),
+            Event::FocusGained => write!(f, "FocusGained"),
+            Event::Char(' ') => write!(f, "Space"),
+            Event::Char(c) => write!(f, "{}", c),
+            Event::CtrlChar(c) => write!(f, "Ctrl+{}", c.to_ascii_uppercase()),
+            Event::AltChar(c) => write!(f, "Alt+{}", c.to_ascii_uppercase()),
+            Event::Key(k) => write!(f, "{}", k),
+            Event::Shift(k) => write!(f, "Shift+{}", k),
+            Event::Alt(k) => write!(f, "Alt+{}", k),
+            Event::AltShift(k) => write!(f, "Alt+Shift+{}", k),
+            Event::Ctrl(k) => write!(f, "Ctrl+{}", k),
+            Event::CtrlShift(k) => write!(f, "Ctrl+Shift+{}", k),
+            Event::CtrlAlt(k) => write!(f, "Ctrl+Alt+{}", k),
+            Event::Mouse { event, .. } => write!(f, "Mouse {:?}", event),
+            Event::Unknown(_) => write!(f, "Unknown"),
+            Event::Exit => write!(f, "Exit"),
+        }
+    }
+}
+
 impl From<char> for Event {
     fn from(c: char) -> Event {
         Event::Char(c)
@@ -0,0 +1,571 @@
+//! Build view trees from a declarative description.
+//!
+//! Instead of nesting constructors in Rust, a layout can be described in a
+//! TOML document, and built at runtime by a [`Registry`].
+//!
+//! # Format
+//!
+//! Each view is a table with a `type` key. Other keys depend on the type.
+//! Child views are nested tables (`content`) or arrays of tables
+//! (`children`).
+//!
+//! ```toml
+//! type = "dialog"
+//! title = "Login"
+//!
+//! [content]
+//! type = "linear"
+//! orientation = "vertical"
+//!
+//! [[content.children]]
+//! type = "text"
+//! content = "Username"
+//! align = "center"
+//!
+//! [[content.children]]
+//! type = "edit"
+//! id = "username"
+//! width = 20
+//!
+//! [[buttons]]
+//! label = "Ok"
+//! callback = "login"
+//!
+//! [[buttons]]
+//! label = "Quit"
+//! callback = "quit"
+//! ```
+//!
+//! Every view accepts these keys:
+//!
+//! * **`id`**: wraps the view in an [`IdView`], so it can be found with
+//!   `Cursive::call_on_id`.
+//! * **`width`** and **`height`**: wraps the view in a [`BoxView`]. The value
+//!   is either a number of cells (fixed size), `"full"`, `"free"`, or a
+//!   table like `{ at_least = 10 }` or `{ at_most = 30 }`.
+//!
+//! The built-in types are:
+//!
+//! * **`text`**: a [`TextView`]. Keys: `content`, `align` (`"left"`,
+//!   `"center"` or `"right"`), `v_align` (`"top"`, `"center"` or
+//!   `"bottom"`) and `scrollable`.
+//! * **`edit`**: an [`EditView`]. Keys: `content`, `secret` and `submit`
+//!   (callback name).
+//! * **`button`**: a [`Button`]. Keys: `label` and `callback`.
+//! * **`checkbox`**: a [`Checkbox`]. Keys: `checked`.
+//! * **`dummy`**: a [`DummyView`].
+//! * **`linear`**: a [`LinearLayout`]. Keys: `orientation` (`"vertical"` or
+//!   `"horizontal"`) and `children`.
+//! * **`panel`**: a [`Panel`]. Keys: `content`.
+//! * **`dialog`**: a [`Dialog`]. Keys: `title`, `content`, `align`
+//!   (for the buttons) and `buttons`, an array of tables with a `label` and
+//!   a `callback`.
+//!
+//! # Callbacks
+//!
+//! Callbacks are referred to by name, and bound with
+//! [`Registry::add_callback`]. `"quit"` and `"dismiss"` (which pops the
+//! current layer) are always available.
+//!
+//! # Custom views
+//!
+//! Applications can register their own types with
+//! [`Registry::add_view_type`]. The builder receives the registry (to build
+//! nested views and find callbacks) and the [`Node`] to build.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # extern crate cursive;
+//! # use cursive::Cursive;
+//! # use cursive::layout::Registry;
+//! # use cursive::views::{Dialog, EditView};
+//! # fn main() {
+//! let mut siv = Cursive::new();
+//!
+//! let registry = Registry::new().callback("login", |s| {
+//!     let name = s.call_on_id("username", |view: &mut EditView| {
+//!             view.get_content()
+//!         })
+//!         .unwrap();
+//!     s.add_layer(Dialog::info(format!("Hello {}!", name)));
+//! });
+//!
+//! siv.add_layer(registry.load_file("assets/login.toml").unwrap());
+//! # }
+//! ```
+//!
+//! [`Registry`]: struct.Registry.html
+//! [`Registry::add_callback`]: struct.Registry.html#method.add_callback
+//! [`Registry::add_view_type`]: struct.Registry.html#method.add_view_type
+//! [`Node`]: struct.Node.html
+//! [`IdView`]: ../views/struct.IdView.html
+//! [`BoxView`]: ../views/struct.BoxView.html
+//! [`TextView`]: ../views/struct.TextView.html
+//! [`EditView`]: ../views/struct.EditView.html
+//! [`Button`]: ../views/struct.Button.html
+//! [`Checkbox`]: ../views/struct.Checkbox.html
+//! [`DummyView`]: ../views/struct.DummyView.html
+//! [`LinearLayout`]: ../views/struct.LinearLayout.html
+//! [`Panel`]: ../views/struct.Panel.html
+//! [`Dialog`]: ../views/struct.Dialog.html
+
+use Cursive;
+use With;
+use align::{HAlign, VAlign};
+use direction::Orientation;
+use event::Callback;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use toml;
+use view::{SizeConstraint, View};
+use views::{BoxView, Button, Checkbox, Dialog, DummyView, EditView, IdView,
+            LinearLayout, Panel, TextView};
+
+/// Possible error returned when building a layout.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occured when reading the file.
+    Io(io::Error),
+    /// An error occured when parsing the toml content.
+    Parse(toml::de::Error),
+    /// A view has no `type` key.
+    MissingType,
+    /// A view uses a type that was not registered.
+    UnknownType(String),
+    /// A callback name was not registered.
+    UnknownCallback(String),
+    /// A required key is missing.
+    MissingKey(String),
+    /// A key has a value that cannot be used.
+    Invalid {
+        /// Key of the entry.
+        key: String,
+        /// Value of the entry, as written in the file.
+        value: String,
+    },
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(err: toml::de::Error) -> Self {
+        Error::Parse(err)
+    }
+}
+
+/// Description of a single view in a layout.
+///
+/// Builders use it to read the view's configuration.
+#[derive(Clone, Copy)]
+pub struct Node<'a> {
+    table: &'a toml::value::Table,
+}
+
+impl<'a> Node<'a> {
+    /// Returns the type of this view.
+    pub fn type_name(&self) -> Result<&'a str, Error> {
+        try!(self.get_str("type")).ok_or(Error::MissingType)
+    }
+
+    /// Returns the string value for the given key, if any.
+    pub fn get_str(&self, key: &str) -> Result<Option<&'a str>, Error> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(&toml::Value::String(ref value)) => Ok(Some(value)),
+            Some(value) => Err(invalid(key, value)),
+        }
+    }
+
+    /// Returns the number value for the given key, if any.
+    pub fn get_usize(&self, key: &str) -> Result<Option<usize>, Error> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(&toml::Value::Integer(value)) if value >= 0 => {
+                Ok(Some(value as usize))
+            }
+            Some(value) => Err(invalid(key, value)),
+        }
+    }
+
+    /// Returns the boolean value for the given key, if any.
+    pub fn get_bool(&self, key: &str) -> Result<Option<bool>, Error> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(&toml::Value::Boolean(value)) => Ok(Some(value)),
+            Some(value) => Err(invalid(key, value)),
+        }
+    }
+
+    /// Returns the nested view for the given key, if any.
+    pub fn get_child(&self, key: &str) -> Result<Option<Node<'a>>, Error> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(&toml::Value::Table(ref table)) => {
+                Ok(Some(Node { table: table }))
+            }
+            Some(value) => Err(invalid(key, value)),
+        }
+    }
+
+    /// Returns the list of nested views for the given key.
+    ///
+    /// Returns an empty list if the key is missing.
+    pub fn get_children(&self, key: &str) -> Result<Vec<Node<'a>>, Error> {
+        let array = match self.table.get(key) {
+            None => return Ok(Vec::new()),
+            Some(&toml::Value::Array(ref array)) => array,
+            Some(value) => return Err(invalid(key, value)),
+        };
+
+        array.iter()
+            .map(|value| match *value {
+                toml::Value::Table(ref table) => Ok(Node { table: table }),
+                ref value => Err(invalid(key, value)),
+            })
+            .collect()
+    }
+
+    /// Wraps `view` according to the common `id`, `width` and `height` keys.
+    pub fn wrap<V>(&self, view: V) -> Result<Box<View>, Error>
+        where V: View + 'static
+    {
+        let width = try!(self.size_constraint("width"));
+        let height = try!(self.size_constraint("height"));
+
+        let view: Box<View> = match try!(self.get_str("id")) {
+            Some(id) => Box::new(IdView::new(id, view)),
+            None => Box::new(view),
+        };
+
+        Ok(match (width, height) {
+            (SizeConstraint::Free, SizeConstraint::Free) => view,
+            (width, height) => Box::new(BoxView::new(width, height, view)),
+        })
+    }
+
+    fn size_constraint(&self, key: &str) -> Result<SizeConstraint, Error> {
+        let value = match self.table.get(key) {
+            None => return Ok(SizeConstraint::Free),
+            Some(value) => value,
+        };
+
+        match *value {
+            toml::Value::Integer(size) if size >= 0 => {
+                return Ok(SizeConstraint::Fixed(size as usize));
+            }
+            toml::Value::String(ref size) if size == "free" => {
+                return Ok(SizeConstraint::Free);
+            }
+            toml::Value::String(ref size) if size == "full" => {
+                return Ok(SizeConstraint::Full);
+            }
+            toml::Value::Table(ref table) if table.len() == 1 => {
+                let node = Node { table: table };
+                if let Some(size) = try!(node.get_usize("at_least")) {
+                    return Ok(SizeConstraint::AtLeast(size));
+                }
+                if let Some(size) = try!(node.get_usize("at_most")) {
+                    return Ok(SizeConstraint::AtMost(size));
+                }
+            }
+            _ => (),
+        }
+
+        Err(invalid(key, value))
+    }
+
+    fn h_align(&self, key: &str) -> Result<Option<HAlign>, Error> {
+        Ok(match try!(self.get_str(key)) {
+            None => None,
+            Some("left") => Some(HAlign::Left),
+            Some("center") => Some(HAlign::Center),
+            Some("right") => Some(HAlign::Right),
+            Some(value) => return Err(invalid_str(key, value)),
+        })
+    }
+
+    fn v_align(&self, key: &str) -> Result<Option<VAlign>, Error> {
+        Ok(match try!(self.get_str(key)) {
+            None => None,
+            Some("top") => Some(VAlign::Top),
+            Some("center") => Some(VAlign::Center),
+            Some("bottom") => Some(VAlign::Bottom),
+            Some(value) => return Err(invalid_str(key, value)),
+        })
+    }
+
+    fn require_str(&self, key: &str) -> Result<&'a str, Error> {
+        try!(self.get_str(key)).ok_or_else(|| Error::MissingKey(key.into()))
+    }
+}
+
+fn invalid(key: &str, value: &toml::Value) -> Error {
+    Error::Invalid {
+        key: key.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn invalid_str(key: &str, value: &str) -> Error {
+    invalid(key, &toml::Value::String(value.to_string()))
+}
+
+type Builder = Box<Fn(&Registry, &Node) -> Result<Box<View>, Error>>;
+
+/// Builds views from a layout description.
+///
+/// It knows the available view types, and the callbacks that can be bound
+/// by name.
+pub struct Registry {
+    builders: HashMap<String, Builder>,
+    callbacks: HashMap<String, Callback>,
+}
+
+new_default!(Registry);
+
+impl Registry {
+    /// Creates a new registry with the built-in view types and callbacks.
+    pub fn new() -> Self {
+        Registry {
+                builders: HashMap::new(),
+                callbacks: HashMap::new(),
+            }
+            .view_type("text", build_text)
+            .view_type("edit", build_edit)
+            .view_type("button", build_button)
+            .view_type("checkbox", build_checkbox)
+            .view_type("dummy", |_, node| node.wrap(DummyView))
+            .view_type("linear", build_linear)
+            .view_type("panel", build_panel)
+            .view_type("dialog", build_dialog)
+            .callback("quit", |s| s.quit())
+            .callback("dismiss", |s| s.screen_mut().pop_layer())
+    }
+
+    /// Registers a new view type.
+    ///
+    /// `builder` should usually end with `node.wrap(view)`, to support the
+    /// common keys. Registering an existing type replaces it.
+    pub fn add_view_type<S, F>(&mut self, name: S, builder: F)
+        where S: Into<String>,
+              F: Fn(&Registry, &Node) -> Result<Box<View>, Error> + 'static
+    {
+        self.builders.insert(name.into(), Box::new(builder));
+    }
+
+    /// Registers a new view type.
+    ///
+    /// Chainable variant.
+    pub fn view_type<S, F>(self, name: S, builder: F) -> Self
+        where S: Into<String>,
+              F: Fn(&Registry, &Node) -> Result<Box<View>, Error> + 'static
+    {
+        self.with(|s| s.add_view_type(name, builder))
+    }
+
+    /// Binds a callback to the given name.
+    pub fn add_callback<S, F>(&mut self, name: S, callback: F)
+        where S: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        self.callbacks.insert(name.into(), Callback::from_fn(callback));
+    }
+
+    /// Binds a callback to the given name.
+    ///
+    /// Chainable variant.
+    pub fn callback<S, F>(self, name: S, callback: F) -> Self
+        where S: Into<String>,
+              F: Fn(&mut Cursive) + 'static
+    {
+        self.with(|s| s.add_callback(name, callback))
+    }
+
+    /// Returns the callback bound to the given name.
+    pub fn get_callback(&self, name: &str) -> Result<Callback, Error> {
+        self.callbacks
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::UnknownCallback(name.to_string()))
+    }
+
+    /// Builds the view described by `node`.
+    pub fn build(&self, node: &Node) -> Result<Box<View>, Error> {
+        let name = try!(node.type_name());
+        match self.builders.get(name) {
+            Some(builder) => builder(self, node),
+            None => Err(Error::UnknownType(name.to_string())),
+        }
+    }
+
+    /// Builds the view described by a TOML string.
+    pub fn load(&self, content: &str) -> Result<Box<View>, Error> {
+        let table: toml::value::Table = try!(toml::de::from_str(content));
+        self.build(&Node { table: &table })
+    }
+
+    /// Builds the view described by a TOML file.
+    pub fn load_file<P: AsRef<Path>>(&self, filename: P)
+                                     -> Result<Box<View>, Error> {
+        let mut content = String::new();
+        let mut file = try!(File::open(filename));
+        try!(file.read_to_string(&mut content));
+        self.load(&content)
+    }
+}
+
+fn build_text(_: &Registry, node: &Node) -> Result<Box<View>, Error> {
+    let mut view = TextView::new(try!(node.get_str("content")).unwrap_or(""));
+    if let Some(h) = try!(node.h_align("align")) {
+        view = view.h_align(h);
+    }
+    if let Some(v) = try!(node.v_align("v_align")) {
+        view = view.v_align(v);
+    }
+    if let Some(scrollable) = try!(node.get_bool("scrollable")) {
+        view.set_scrollable(scrollable);
+    }
+    node.wrap(view)
+}
+
+fn build_edit(registry: &Registry, node: &Node) -> Result<Box<View>, Error> {
+    let mut view = EditView::new();
+    if let Some(content) = try!(node.get_str("content")) {
+        view.set_content(content);
+    }
+    if let Some(secret) = try!(node.get_bool("secret")) {
+        view.set_secret(secret);
+    }
+    if let Some(name) = try!(node.get_str("submit")) {
+        let callback = try!(registry.get_callback(name));
+        view.set_on_submit(move |s, _| callback(s));
+    }
+    node.wrap(view)
+}
+
+fn build_button(registry: &Registry, node: &Node) -> Result<Box<View>, Error> {
+    let label = try!(node.require_str("label"));
+    let name = try!(node.require_str("callback"));
+    let callback = try!(registry.get_callback(name));
+    node.wrap(Button::new(label, move |s| callback(s)))
+}
+
+fn build_checkbox(_: &Registry, node: &Node) -> Result<Box<View>, Error> {
+    let mut view = Checkbox::new();
+    if let Some(checked) = try!(node.get_bool("checked")) {
+        view.set_checked(checked);
+    }
+    node.wrap(view)
+}
+
+fn build_linear(registry: &Registry, node: &Node) -> Result<Box<View>, Error> {
+    let orientation = match try!(node.get_str("orientation")) {
+        None | Some("vertical") => Orientation::Vertical,
+        Some("horizontal") => Orientation::Horizontal,
+        Some(value) => return Err(invalid_str("orientation", value)),
+    };
+
+    let mut view = LinearLayout::new(orientation);
+    for child in try!(node.get_children("children")) {
+        view.add_child(try!(registry.build(&child)));
+    }
+    node.wrap(view)
+}
+
+fn build_panel(registry: &Registry, node: &Node) -> Result<Box<View>, Error> {
+    let content = match try!(node.get_child("content")) {
+        Some(content) => try!(registry.build(&content)),
+        None => return Err(Error::MissingKey("content".to_string())),
+    };
+    node.wrap(Panel::new(content))
+}
+
+fn build_dialog(registry: &Registry, node: &Node) -> Result<Box<View>, Error> {
+    let mut view = Dialog::new();
+    if let Some(content) = try!(node.get_child("content")) {
+        view.set_content(try!(registry.build(&content)));
+    }
+    if let Some(title) = try!(node.get_str("title")) {
+        view.set_title(title);
+    }
+    if let Some(h) = try!(node.h_align("align")) {
+        view = view.h_align(h);
+    }
+    for button in try!(node.get_children("buttons")) {
+        let label = try!(button.require_str("label"));
+        let name = try!(button.require_str("callback"));
+        let callback = try!(registry.get_callback(name));
+        view = view.button(label, move |s| callback(s));
+    }
+    node.wrap(view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use view::Finder;
+
+    #[test]
+    fn test_errors() {
+        let registry = Registry::new();
+
+        match registry.load("type = \"table\"") {
+            Err(Error::UnknownType(ref name)) if name == "table" => (),
+            _ => panic!("table should not be a known type"),
+        }
+        match registry.load("content = \"Hello\"") {
+            Err(Error::MissingType) => (),
+            _ => panic!("a view without type should be rejected"),
+        }
+        match registry.load("type = \"button\"\nlabel = \"Ok\"\n\
+                             callback = \"ok\"") {
+            Err(Error::UnknownCallback(ref name)) if name == "ok" => (),
+            _ => panic!("ok should not be a known callback"),
+        }
+        match registry.load("type = \"text\"\nwidth = \"wide\"") {
+            Err(Error::Invalid { ref key, .. }) if key == "width" => (),
+            _ => panic!("wide should not be a valid width"),
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let content = r#"
+            type = "dialog"
+            title = "Login"
+
+            [content]
+            type = "linear"
+
+            [[content.children]]
+            type = "edit"
+            id = "name"
+            width = { at_least = 10 }
+
+            [[content.children]]
+            type = "greeting"
+
+            [[buttons]]
+            label = "Ok"
+            callback = "dismiss"
+        "#;
+
+        let registry = Registry::new().view_type("greeting", |_, node| {
+            node.wrap(TextView::new("Hello!"))
+        });
+
+        let mut view = registry.load(content).unwrap();
+        let found = view.find_id("name", |_: &mut EditView| ());
+        assert!(found.is_some());
+
+        let registry = registry.callback("login", |_| ());
+        assert!(registry.load(include_str!("../assets/login.toml")).is_ok());
+    }
+}
@@ -91,8 +91,11 @@ pub mod view;
 pub mod views;
 pub mod vec;
 pub mod theme;
+pub mod layout;
+pub mod observable;
 pub mod align;
 pub mod menu;
+pub mod notification;
 pub mod direction;
 pub mod utils;
 
@@ -108,21 +111,32 @@ mod utf8;
 pub mod backend;
 
 
+use align::{Align, VAlign};
 use backend::Backend;
 
-use event::{Callback, Event, EventResult};
+use event::{Callback, Event, EventResult, Key};
+use menu::MenuTree;
+use notification::Notification;
+use observable::Observable;
+use theme::ColorStyle;
 
 pub use printer::Printer;
 
 use std::any::Any;
 use std::collections::HashMap;
-use std::path::Path;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Instant, SystemTime};
 
 use std::sync::mpsc;
 
 use vec::Vec2;
 use view::Finder;
+use view::Position;
 use view::View;
+use views::MenuPopup;
 pub use with::With;
 pub use xy::XY;
 
@@ -141,7 +155,30 @@ pub struct Cursive {
     theme: theme::Theme,
     screens: Vec<views::StackView>,
     global_callbacks: HashMap<Event, Callback>,
+    // Names given to some global callbacks, to list them as commands.
+    global_callback_names: HashMap<Event, String>,
+    // Explicit tab order, by view id.
+    tab_indices: HashMap<String, usize>,
+    // Shared values, by name. Each one is an `Observable`.
+    observables: HashMap<String, Box<Any>>,
+    // Updates bound views when their observable changes, by view id.
+    bindings: Vec<(String, Box<FnMut(&mut Cursive)>)>,
+    // Draw the view tree inspector over the current screen?
+    inspector: bool,
     menubar: views::Menubar,
+    statusbar: views::StatusBar,
+    statusbar_visible: bool,
+
+    // Toasts currently shown, with the time they should disappear.
+    notifications: Vec<(Instant, Notification)>,
+    notification_align: Align,
+
+    // Theme file to reload when it changes, with its last modification time.
+    theme_file: Option<(PathBuf, Option<SystemTime>)>,
+
+    // Refresh rate requested by the user.
+    // We may temporarily go faster to expire notifications.
+    fps: u32,
 
     // Last layer sizes of the stack view.
     // If it changed, clear the screen.
@@ -164,18 +201,25 @@ impl Cursive {
     pub fn new() -> Self {
         let backend = backend::Concrete::init();
 
-        let theme = theme::load_default();
-        // theme.activate(&mut backend);
-        // let theme = theme::load_theme("assets/style.toml").unwrap();
-
         let (tx, rx) = mpsc::channel();
 
         let mut res = Cursive {
-            theme: theme,
+            theme: theme::load_default(),
             screens: Vec::new(),
             last_sizes: Vec::new(),
             global_callbacks: HashMap::new(),
+            global_callback_names: HashMap::new(),
+            tab_indices: HashMap::new(),
+            observables: HashMap::new(),
+            bindings: Vec::new(),
+            inspector: false,
             menubar: views::Menubar::new(),
+            statusbar: views::StatusBar::new(),
+            statusbar_visible: false,
+            notifications: Vec::new(),
+            notification_align: Align::bot_right(),
+            theme_file: None,
+            fps: 0,
             active_screen: 0,
             running: true,
             cb_source: rx,
@@ -184,6 +228,8 @@ impl Cursive {
         };
 
         res.screens.push(views::StackView::new());
+        // Without colors, this falls back to the monochrome palette.
+        res.set_theme(theme::load_default());
 
         res
     }
@@ -217,6 +263,28 @@ impl Cursive {
         self.menubar.autohide = autohide;
     }
 
+    /// Shows or hides the status bar.
+    ///
+    /// When visible, the status bar reserves the bottom row of the screen.
+    /// It is hidden by default.
+    pub fn set_statusbar_visible(&mut self, visible: bool) {
+        self.statusbar_visible = visible;
+        self.clear();
+    }
+
+    /// Returns `true` if the status bar is visible.
+    pub fn is_statusbar_visible(&self) -> bool {
+        self.statusbar_visible
+    }
+
+    /// Access the status bar.
+    ///
+    /// Callbacks can use this to show hints or indicators,
+    /// for instance the keys available in the focused view.
+    pub fn statusbar(&mut self) -> &mut views::StatusBar {
+        &mut self.statusbar
+    }
+
     /// Access the menu tree used by the menubar.
     ///
     /// This allows to add menu items to the menubar.
@@ -267,6 +335,28 @@ impl Cursive {
     /// siv.add_global_callback(event::Key::Esc, |s| s.select_menubar());
     /// # }
     /// ```
+    ///
+    /// Leaves can carry a shortcut, shown in the menu and triggered
+    /// automatically when no view or global callback uses the event.
+    /// Titles in the menubar can set a mnemonic with `&`:
+    ///
+    /// ```no_run
+    /// # extern crate cursive;
+    /// #
+    /// # use cursive::{Cursive, event};
+    /// # use cursive::menu::*;
+    /// #
+    /// # fn main() {
+    /// let mut siv = Cursive::new();
+    ///
+    /// // <Alt-F> opens this menu, <Ctrl-Q> quits.
+    /// siv.menubar()
+    ///    .add_subtree("&File",
+    ///         MenuTree::new()
+    ///             .shortcut_leaf("Quit", event::Event::CtrlChar('q'),
+    ///                            |s| s.quit()));
+    /// # }
+    /// ```
     pub fn menubar(&mut self) -> &mut views::Menubar {
         &mut self.menubar
     }
@@ -277,16 +367,40 @@ impl Cursive {
     }
 
     /// Sets the current theme.
-    pub fn set_theme(&mut self, theme: theme::Theme) {
+    ///
+    /// The whole screen is redrawn with the new colors.
+    ///
+    /// If the terminal has fewer than 8 colors, the palette from the
+    /// `monochrome` preset is used instead.
+    pub fn set_theme(&mut self, mut theme: theme::Theme) {
+        if self.backend.color_count() < 8 {
+            if let Some(monochrome) = theme::preset("monochrome") {
+                theme.colors = monochrome.colors;
+            }
+        }
         self.theme = theme;
+        self.backend.reset_colors();
         self.clear();
     }
 
+    /// Sets the current theme to the built-in preset with the given name.
+    ///
+    /// Returns `Err(())` if there is no such preset.
+    /// See [`theme::PRESETS`] for the list of names.
+    ///
+    /// [`theme::PRESETS`]: theme/constant.PRESETS.html
+    pub fn set_theme_preset(&mut self, name: &str) -> Result<(), ()> {
+        let theme = try!(theme::preset(name).ok_or(()));
+        self.set_theme(theme);
+        Ok(())
+    }
+
     /// Clears the screen.
     ///
     /// Users rarely have to call this directly.
     pub fn clear(&self) {
-        self.backend.clear(self.theme.colors.background);
+        let colors = self.backend.color_count();
+        self.backend.clear(self.theme.colors.background.nearest(colors));
     }
 
     /// Loads a theme from the given file.
@@ -306,6 +420,53 @@ impl Cursive {
         Ok(())
     }
 
+    /// Loads a theme from the given file, and reloads it when it changes.
+    ///
+    /// The file is checked regularly, even without any input.
+    /// If it becomes invalid, the current theme is kept until it is fixed.
+    ///
+    /// Only one file is watched at a time.
+    pub fn watch_theme_file<P: AsRef<Path>>(&mut self, filename: P)
+                                            -> Result<(), theme::Error> {
+        let path = filename.as_ref().to_path_buf();
+        let modified = modified_time(&path);
+        try!(self.load_theme_file(&path));
+
+        self.theme_file = Some((path, modified));
+        self.update_refresh_rate();
+        Ok(())
+    }
+
+    /// Stops watching the theme file given to [`watch_theme_file`].
+    ///
+    /// The current theme is kept.
+    ///
+    /// [`watch_theme_file`]: #method.watch_theme_file
+    pub fn unwatch_theme_file(&mut self) {
+        self.theme_file = None;
+        self.update_refresh_rate();
+    }
+
+    // Reloads the watched theme file if it changed since last time.
+    fn reload_theme_file(&mut self) {
+        let path = match self.theme_file {
+            Some((ref path, ref mut last)) => {
+                let modified = modified_time(path);
+                if modified == *last {
+                    return;
+                }
+                *last = modified;
+                path.clone()
+            }
+            None => return,
+        };
+
+        match theme::load_theme_file(&path) {
+            Ok(theme) => self.set_theme(theme),
+            Err(err) => warn!("Cannot reload theme {:?}: {:?}", path, err),
+        }
+    }
+
     /// Sets the refresh rate, in frames per second.
     ///
     /// Regularly redraws everything, even when no input is given.
@@ -317,7 +478,120 @@ impl Cursive {
     ///
     /// [`cb_sink`]: #method.cb_sink
     pub fn set_fps(&mut self, fps: u32) {
-        self.backend.set_refresh_rate(fps)
+        self.fps = fps;
+        self.update_refresh_rate();
+    }
+
+    /// Enables or disables mouse events.
+    ///
+    /// The mouse is not captured by default: while it is, most terminals
+    /// don't let the user select text with it anymore.
+    ///
+    /// Only the ncurses and termion backends report mouse events.
+    pub fn set_mouse_capture(&mut self, enabled: bool) {
+        self.backend.set_mouse_capture(enabled);
+    }
+
+    // Wakes up regularly while notifications wait to expire,
+    // or to check the theme file.
+    fn update_refresh_rate(&mut self) {
+        let waiting = !self.notifications.is_empty() ||
+                      self.theme_file.is_some();
+        let fps = if self.fps == 0 && waiting {
+            4
+        } else {
+            self.fps
+        };
+        self.backend.set_refresh_rate(fps);
+    }
+
+    /// Shows a notification on top of the current screen.
+    ///
+    /// It will not take the focus, and will disappear after its duration.
+    /// Accepts either a [`Notification`], or a string for simple
+    /// informational messages.
+    ///
+    /// To post notifications from another thread, send a callback through
+    /// [`cb_sink`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate cursive;
+    /// # use cursive::Cursive;
+    /// # use cursive::notification::Notification;
+    /// # fn main() {
+    /// let mut siv = Cursive::new();
+    /// siv.set_fps(10);
+    ///
+    /// let sink = siv.cb_sink().clone();
+    /// std::thread::spawn(move || {
+    ///     // ... do some work ...
+    ///     sink.send(Box::new(|s: &mut Cursive| {
+    ///         s.notify(Notification::warning("Job finished with errors"))
+    ///     })).unwrap();
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// [`Notification`]: notification/struct.Notification.html
+    /// [`cb_sink`]: #method.cb_sink
+    pub fn notify<N: Into<Notification>>(&mut self, notification: N) {
+        let notification = notification.into();
+        let deadline = Instant::now() + notification.get_duration();
+        self.notifications.push((deadline, notification));
+        self.update_refresh_rate();
+    }
+
+    /// Removes all notifications currently shown.
+    pub fn clear_notifications(&mut self) {
+        if !self.notifications.is_empty() {
+            self.notifications.clear();
+            self.update_refresh_rate();
+            self.clear();
+        }
+    }
+
+    /// Sets the screen corner where notifications are shown.
+    ///
+    /// Defaults to the bottom-right corner.
+    pub fn set_notification_align(&mut self, align: Align) {
+        self.notification_align = align;
+        self.clear();
+    }
+
+    // Removes notifications past their deadline.
+    fn expire_notifications(&mut self) {
+        let now = Instant::now();
+        let before = self.notifications.len();
+        self.notifications.retain(|&(deadline, _)| deadline > now);
+        if self.notifications.len() != before {
+            self.update_refresh_rate();
+            // Toasts are drawn over the screen: repaint what was below.
+            self.clear();
+        }
+    }
+
+    // Draws toasts in a corner, the most recent closest to the corner.
+    fn draw_notifications(notifications: &mut [(Instant, Notification)],
+                          align: &Align, printer: &Printer) {
+        let mut used = 0;
+        for &mut (_, ref mut notification) in notifications.iter_mut().rev() {
+            let available = printer.size.saturating_sub((0, used));
+            let size = notification.required_size(available);
+            if !size.fits_in(available) {
+                break;
+            }
+
+            let x = align.h.get_offset(size.x, printer.size.x);
+            let y = match align.v {
+                VAlign::Bottom => printer.size.y - used - size.y,
+                _ => used,
+            };
+            used += size.y;
+
+            notification.draw(&printer.sub_printer((x, y), size, false));
+        }
     }
 
     /// Returns a reference to the currently active screen.
@@ -434,6 +708,95 @@ impl Cursive {
         self.call_on_id(id, views::IdView::<V>::get_mut)
     }
 
+    /// Stores a new observable value under the given name.
+    ///
+    /// Returns a handle to this value. Any previous observable with the same
+    /// name is replaced.
+    pub fn add_observable<S, T>(&mut self, name: S, value: T) -> Observable<T>
+        where S: Into<String>,
+              T: 'static
+    {
+        let observable = Observable::new(value);
+        self.observables.insert(name.into(), Box::new(observable.clone()));
+        observable
+    }
+
+    /// Returns the observable stored under the given name.
+    ///
+    /// Returns `None` if no such observable exists, or if it holds another
+    /// type of value.
+    pub fn observable<T: 'static>(&self, name: &str) -> Option<Observable<T>> {
+        self.observables
+            .get(name)
+            .and_then(|observable| observable.downcast_ref::<Observable<T>>())
+            .cloned()
+    }
+
+    /// Removes the observable stored under the given name.
+    ///
+    /// Existing handles and bindings keep working.
+    pub fn remove_observable(&mut self, name: &str) {
+        self.observables.remove(name);
+    }
+
+    /// Binds the view identified by `id` to an observable value.
+    ///
+    /// `f` is called on the view on the next frame, and again on the frame
+    /// following each change of the value. If the view cannot be found, this
+    /// is tried again on the next frame: a view removed and later added
+    /// back with the same id is updated again.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate cursive;
+    /// # use cursive::{Cursive, views};
+    /// # use cursive::view::Identifiable;
+    /// # fn main() {
+    /// let mut siv = Cursive::new();
+    ///
+    /// let count = siv.add_observable("count", 0);
+    /// siv.bind(&count, "text", |view: &mut views::TextView, count: &i32| {
+    ///     view.set_content(format!("Count: {}", count));
+    /// });
+    ///
+    /// siv.add_layer(views::TextView::empty().with_id("text"));
+    /// siv.add_global_callback('+', move |_| count.update(|c| *c += 1));
+    /// # }
+    /// ```
+    pub fn bind<T, V, F>(&mut self, observable: &Observable<T>, id: &str,
+                         f: F)
+        where T: 'static,
+              V: View + Any,
+              F: Fn(&mut V, &T) + 'static
+    {
+        let mut binding = Binding {
+            observable: observable.clone(),
+            target: id.to_string(),
+            applied: None,
+            f: f,
+            _view: PhantomData,
+        };
+        let update = move |s: &mut Cursive| binding.update(s.screen_mut());
+        self.bindings.push((id.to_string(), Box::new(update)));
+    }
+
+    /// Removes all bindings for the view identified by `id`.
+    pub fn unbind(&mut self, id: &str) {
+        self.bindings.retain(|&(ref bound, _)| bound != id);
+    }
+
+    // Updates views bound to a changed observable.
+    fn update_bindings(&mut self) {
+        let mut bindings = self.bindings.split_off(0);
+        for &mut (_, ref mut binding) in &mut bindings {
+            binding(self);
+        }
+        // Keep any binding added while updating.
+        bindings.append(&mut self.bindings);
+        self.bindings = bindings;
+    }
+
     /// Moves the focus to the view identified by `id`.
     ///
     /// Convenient method to call `focus` with a `view::Selector::Id`.
@@ -442,8 +805,149 @@ impl Cursive {
     }
 
     /// Moves the focus to the view identified by `sel`.
+    ///
+    /// Views losing and gaining the focus receive `Event::FocusLost` and
+    /// `Event::FocusGained`. Nothing is sent if the target refuses the
+    /// focus.
     pub fn focus(&mut self, sel: &view::Selector) -> Result<(), ()> {
-        self.screen_mut().focus_view(sel)
+        let result = try!(move_focus_to(self.screen_mut(), sel));
+        result.process(self);
+
+        Ok(())
+    }
+
+    /// Returns the id of the focused view, if any.
+    ///
+    /// This is the innermost [`IdView`] containing the focus.
+    ///
+    /// [`IdView`]: views/struct.IdView.html
+    pub fn focused_id(&self) -> Option<String> {
+        self.screen().focused_id()
+    }
+
+    /// Returns the path to the focused view in the current screen.
+    ///
+    /// The first element is the index of the top layer. Use it with
+    /// `view::Selector::Path` to find the view again.
+    pub fn focused_path(&self) -> view::ViewPath {
+        self.screen().focused_path()
+    }
+
+    /// Returns the id of the view at the given path, if it has one.
+    ///
+    /// `path` is relative to the current screen, as returned by
+    /// [`focused_path`].
+    ///
+    /// [`focused_path`]: #method.focused_path
+    pub fn id_at(&self, path: &view::ViewPath) -> Option<String> {
+        self.screen().id_at(path)
+    }
+
+    /// Describes the view tree of the current screen.
+    ///
+    /// Each view comes with its type, id, size and offset. Use `to_string()`
+    /// on the result to dump the tree as text.
+    pub fn inspect(&self) -> view::ViewInfo {
+        self.screen().inspect(self.screen_area())
+    }
+
+    /// Shows or hides the view tree inspector.
+    ///
+    /// When shown, every view in the current screen is labeled with its
+    /// type, id and size, and the focus path is printed at the bottom.
+    /// The views on the focus path are highlighted.
+    ///
+    /// When the inspector is shown, the view tree is also written to the
+    /// log.
+    pub fn toggle_inspector(&mut self) {
+        self.inspector = !self.inspector;
+        if self.inspector {
+            info!("View tree:\n{}", self.inspect());
+        }
+        self.clear();
+    }
+
+    /// Returns `true` if the view tree inspector is shown.
+    pub fn is_inspector_visible(&self) -> bool {
+        self.inspector
+    }
+
+    /// Toggles the view tree inspector when `event` is received.
+    ///
+    /// This is a global callback, listed as "Toggle inspector" in the
+    /// command palette.
+    pub fn set_inspector_key<E: Into<Event>>(&mut self, event: E) {
+        self.add_named_global_callback("Toggle inspector",
+                                       event,
+                                       Cursive::toggle_inspector);
+    }
+
+    /// Moves the focus to the next view.
+    ///
+    /// Follows the explicit tab order if the focused view is part of it,
+    /// otherwise this is the same as pressing `<Tab>`.
+    ///
+    /// Returns `Err(())` if the focus could not move.
+    pub fn focus_next(&mut self) -> Result<(), ()> {
+        self.move_focus(true)
+    }
+
+    /// Moves the focus to the previous view.
+    ///
+    /// Follows the explicit tab order if the focused view is part of it,
+    /// otherwise this is the same as pressing `<Shift-Tab>`.
+    ///
+    /// Returns `Err(())` if the focus could not move.
+    pub fn focus_previous(&mut self) -> Result<(), ()> {
+        self.move_focus(false)
+    }
+
+    /// Sets the position of the view identified by `id` in the tab order.
+    ///
+    /// When the focused view has a tab index, `<Tab>` and `<Shift-Tab>`
+    /// move to the view with the next (or previous) index in the top
+    /// layer, wrapping around. Views without an index keep the default
+    /// traversal of their container.
+    pub fn set_tab_index(&mut self, id: &str, index: usize) {
+        self.tab_indices.insert(id.to_string(), index);
+    }
+
+    /// Removes the view identified by `id` from the tab order.
+    pub fn clear_tab_index(&mut self, id: &str) {
+        self.tab_indices.remove(id);
+    }
+
+    fn move_focus(&mut self, forward: bool) -> Result<(), ()> {
+        if let Some(id) = self.next_tab_stop(forward) {
+            return self.focus_id(&id);
+        }
+
+        let event = if forward {
+            Event::Key(Key::Tab)
+        } else {
+            Event::Shift(Key::Tab)
+        };
+        let result = self.screen_mut().on_event(event);
+        if result.is_consumed() {
+            result.process(self);
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+
+    fn next_tab_stop(&mut self, forward: bool) -> Option<String> {
+        let screen = &mut self.screens[self.active_screen];
+        next_tab_stop(&self.tab_indices, screen, forward)
+    }
+
+    // Returns the view to focus if `event` should follow the tab order.
+    fn tab_stop_for(&mut self, event: &Event) -> Option<String> {
+        match *event {
+            Event::Key(Key::Tab) => self.next_tab_stop(true),
+            Event::Shift(Key::Tab) => self.next_tab_stop(false),
+            _ => None,
+        }
     }
 
     /// Adds a global callback.
@@ -464,7 +968,75 @@ impl Cursive {
     pub fn add_global_callback<F, E: Into<Event>>(&mut self, event: E, cb: F)
         where F: Fn(&mut Cursive) + 'static
     {
-        self.global_callbacks.insert(event.into(), Callback::from_fn(cb));
+        let event = event.into();
+        self.global_callback_names.remove(&event);
+        self.global_callbacks.insert(event, Callback::from_fn(cb));
+    }
+
+    /// Adds a global callback with a name.
+    ///
+    /// Works like [`add_global_callback`], but the callback will also be
+    /// listed under `name` in the [command palette].
+    ///
+    /// [`add_global_callback`]: #method.add_global_callback
+    /// [command palette]: #method.show_command_palette
+    pub fn add_named_global_callback<S, F, E>(&mut self, name: S, event: E,
+                                              cb: F)
+        where S: Into<String>,
+              F: Fn(&mut Cursive) + 'static,
+              E: Into<Event>
+    {
+        let event = event.into();
+        self.add_global_callback(event.clone(), cb);
+        self.global_callback_names.insert(event, name.into());
+    }
+
+    /// Returns every command that can be launched from the command palette.
+    ///
+    /// This includes all the leaves from the menubar, followed by the named
+    /// global callbacks sorted by name.
+    pub fn commands(&self) -> Vec<views::Command> {
+        let mut commands: Vec<_> = self.menubar
+            .subtrees()
+            .flat_map(|(label, tree)| views::Command::from_menu(label, tree))
+            .collect();
+
+        let mut named: Vec<_> = self.global_callback_names
+            .iter()
+            .map(|(event, name)| {
+                views::Command {
+                    label: name.clone(),
+                    key: Some(event.clone()),
+                    callback: self.global_callbacks[event].clone(),
+                }
+            })
+            .collect();
+        named.sort_by(|a, b| a.label.cmp(&b.label));
+        commands.extend(named);
+
+        commands
+    }
+
+    /// Opens a command palette listing all [`commands`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # extern crate cursive;
+    /// # use cursive::*;
+    /// # fn main() {
+    /// let mut siv = Cursive::new();
+    ///
+    /// siv.add_named_global_callback("Quit", 'q', |s| s.quit());
+    /// siv.add_global_callback(event::Event::CtrlChar('p'),
+    ///                         |s| s.show_command_palette());
+    /// # }
+    /// ```
+    ///
+    /// [`commands`]: #method.commands
+    pub fn show_command_palette(&mut self) {
+        let palette = views::CommandPalette::new(self.commands());
+        self.add_layer(views::Panel::new(palette));
     }
 
     /// Add a layer to the current screen.
@@ -482,6 +1054,7 @@ impl Cursive {
     /// ```
     pub fn add_layer<T: 'static + View>(&mut self, view: T) {
         self.screen_mut().add_layer(view);
+        self.process_focus_events();
     }
 
     /// Adds a new full-screen layer to the current screen.
@@ -491,24 +1064,61 @@ impl Cursive {
         where T: 'static + View
     {
         self.screen_mut().add_fullscreen_layer(view);
+        self.process_focus_events();
+    }
+
+    /// Opens a popup menu at the given position.
+    ///
+    /// `position` is in screen coordinates, as reported by mouse events.
+    /// The popup is moved if needed to stay inside the screen.
+    pub fn show_menu_at(&mut self, position: Vec2, menu: Rc<MenuTree>) {
+        let position = position.saturating_sub(self.screen_offset());
+        self.screen_mut()
+            .add_layer_at(Position::absolute(position), MenuPopup::new(menu));
+        self.process_focus_events();
     }
 
     /// Convenient method to remove a layer from the current screen.
     pub fn pop_layer(&mut self) {
         self.screen_mut().pop_layer();
+        self.process_focus_events();
         self.clear();
     }
 
+    // Runs the callbacks from layers losing or gaining the focus.
+    fn process_focus_events(&mut self) {
+        let result = self.screen_mut().take_focus_events();
+        result.process(self);
+    }
+
     // Handles a key event when it was ignored by the current view
     fn on_event(&mut self, event: Event) {
+        // Explicit global callbacks come first,
+        // then shortcuts from the menubar leaves.
         let cb = match self.global_callbacks.get(&event) {
-            None => return,
             Some(cb) => cb.clone(),
+            None => match self.menubar.find_shortcut(&event) {
+                Some(cb) => cb,
+                None => {
+                    if let Event::AltChar(c) = event {
+                        self.open_menu_mnemonic(c);
+                    }
+                    return;
+                }
+            },
         };
         // Not from a view, so no viewpath here
         cb(self);
     }
 
+    // Opens the menubar item with the given mnemonic, if any.
+    fn open_menu_mnemonic(&mut self, c: char) {
+        if self.menubar.find_mnemonic(c).is_some() {
+            self.select_menubar();
+            self.menubar.on_event(Event::AltChar(c)).process(self);
+        }
+    }
+
     /// Returns the size of the screen, in characters.
     pub fn screen_size(&self) -> Vec2 {
         let (x, y) = self.backend.screen_size();
@@ -519,13 +1129,31 @@ impl Cursive {
         }
     }
 
+    // Position of the active screen, below the menubar if it stays visible.
+    fn screen_offset(&self) -> Vec2 {
+        Vec2::new(0, if self.menubar.autohide { 0 } else { 1 })
+    }
+
+    // Size left for the active screen by the menubar and the status bar.
+    fn screen_area(&self) -> Vec2 {
+        let bottom = if self.statusbar_visible { 1 } else { 0 };
+        self.screen_size().saturating_sub(self.screen_offset() + (0, bottom))
+    }
+
     fn layout(&mut self) {
-        let size = self.screen_size();
+        let size = self.screen_area();
         self.screen_mut().layout(size);
+
+        if self.statusbar_visible {
+            let width = self.screen_size().x;
+            self.statusbar.layout(Vec2::new(width, 1));
+        }
     }
 
     fn draw(&mut self) {
-        let sizes = self.screen().layer_sizes();
+        // Clear the screen when a layer is resized or moved.
+        let mut sizes = self.screen().layer_sizes();
+        sizes.extend(self.screen().layer_offsets());
         if self.last_sizes != sizes {
             self.clear();
             self.last_sizes = sizes;
@@ -537,7 +1165,7 @@ impl Cursive {
 
         // Draw the currently active screen
         // If the menubar is active, nothing else can be.
-        let offset = if self.menubar.autohide { 0 } else { 1 };
+        let offset = self.screen_offset();
         // Draw the menubar?
         if self.menubar.visible() {
             let printer = printer.sub_printer(Vec2::zero(),
@@ -546,13 +1174,45 @@ impl Cursive {
             self.menubar.draw(&printer);
         }
 
+        if self.statusbar_visible && printer.size.y > 0 {
+            let y = printer.size.y - 1;
+            let printer =
+                printer.sub_printer((0, y), (printer.size.x, 1), false);
+            self.statusbar.draw(&printer);
+        }
+
         let selected = self.menubar.receive_events();
 
         let printer =
-            printer.sub_printer(Vec2::new(0, offset), printer.size, !selected);
+            printer.sub_printer(offset, self.screen_area(), !selected);
         let id = self.active_screen;
         self.screens[id].draw(&printer);
 
+        Cursive::draw_notifications(&mut self.notifications,
+                                    &self.notification_align,
+                                    &printer);
+
+        if self.inspector {
+            let info = self.screens[id].inspect(printer.size);
+            draw_inspector(&info, true, &printer);
+
+            let path = self.focused_path();
+            let mut focus = path.path
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            if let Some(id) = self.focused_id() {
+                focus.push_str(&format!(" #{}", id));
+            }
+            if printer.size.y > 0 {
+                printer.with_color(ColorStyle::Secondary, |printer| {
+                    let y = printer.size.y - 1;
+                    printer.print_hline((0, y), printer.size.x, " ");
+                    printer.print((0, y), &format!("Focus: /{}", focus));
+                });
+            }
+        }
     }
 
     /// Returns `true` until [`quit(&mut self)`] is called.
@@ -595,6 +1255,12 @@ impl Cursive {
             cb(self);
         }
 
+        // Layers may have been changed directly on the screen.
+        self.process_focus_events();
+        self.expire_notifications();
+        self.reload_theme_file();
+        self.update_bindings();
+
         // Do we need to redraw everytime?
         // Probably, actually.
         // TODO: Do we need to re-layout everytime?
@@ -621,8 +1287,15 @@ impl Cursive {
         //     * Menubar (if active)
         //     * Current screen (top layer)
         // * Global callbacks
+        // Mouse events on the status bar go directly there,
+        // and tab stops take precedence over the focused view.
         if self.menubar.receive_events() {
             self.menubar.on_event(event).process(self);
+        } else if self.is_statusbar_event(&event) {
+            self.statusbar.on_event(event).process(self);
+        } else if let Some(id) = self.tab_stop_for(&event) {
+            // The target may be disabled: then the focus doesn't move.
+            let _ = self.focus_id(&id);
         } else {
             match self.screen_mut().on_event(event.clone()) {
                 // If the event was ignored,
@@ -634,14 +1307,264 @@ impl Cursive {
         }
     }
 
+    fn is_statusbar_event(&self, event: &Event) -> bool {
+        match *event {
+            Event::Mouse { position, .. } => {
+                self.statusbar_visible &&
+                position.y + 1 == self.screen_size().y
+            }
+            _ => false,
+        }
+    }
+
     /// Stops the event loop.
     pub fn quit(&mut self) {
         self.running = false;
     }
 }
 
+// Applies an observable value to the view with the given id.
+struct Binding<T, V, F> {
+    observable: Observable<T>,
+    target: String,
+    // Version of the value last applied to the view.
+    applied: Option<usize>,
+    f: F,
+    _view: PhantomData<V>,
+}
+
+impl<T, V, F> Binding<T, V, F>
+    where V: View + Any,
+          F: Fn(&mut V, &T)
+{
+    // Calls `f` if the value changed since it was last applied.
+    fn update(&mut self, root: &mut views::StackView) {
+        let version = self.observable.version();
+        if self.applied == Some(version) {
+            // Forget it if the view is gone: it may come back later.
+            if root.find_id(&self.target, |_: &mut V| ()).is_none() {
+                self.applied = None;
+            }
+            return;
+        }
+
+        let value = self.observable.borrow();
+        let f = &self.f;
+        if root.find_id(&self.target, |view: &mut V| f(view, &value))
+            .is_some() {
+            self.applied = Some(version);
+        }
+    }
+}
+
+// Moves the focus in `screen`, notifying the views losing and gaining it.
+//
+// Nothing is notified if the focus stays on the same view, for instance
+// when the target is in a lower layer.
+fn move_focus_to(screen: &mut views::StackView, sel: &view::Selector)
+                 -> Result<EventResult, ()> {
+    let old = screen.focused_path();
+    try!(screen.focus_view(sel));
+    let new = screen.focused_path();
+    if new == old {
+        return Ok(EventResult::Ignored);
+    }
+
+    // `FocusLost` follows the focus, so go back to the old view to send it.
+    let _ = screen.focus_view(&view::Selector::Path(&old));
+    let lost = screen.on_event(Event::FocusLost);
+    let _ = screen.focus_view(&view::Selector::Path(&new));
+    let gained = screen.on_event(Event::FocusGained);
+
+    Ok(lost.and(gained))
+}
+
+// Returns the id of the view to focus after (or before) the focused one,
+// if it has a tab index.
+fn next_tab_stop(tab_indices: &HashMap<String, usize>,
+                 screen: &mut views::StackView, forward: bool)
+                 -> Option<String> {
+    let current = match screen.focused_id() {
+        Some(ref id) if tab_indices.contains_key(id) => id.clone(),
+        _ => return None,
+    };
+
+    // Only consider views from the top layer.
+    let top = screen.len().saturating_sub(1);
+    let mut stops: Vec<(usize, String)> = tab_indices
+        .iter()
+        .map(|(id, &index)| (index, id.clone()))
+        .collect();
+    stops.retain(|&(_, ref id)| {
+        *id == current || screen.find_layer_from_id(id) == Some(top)
+    });
+    stops.sort();
+
+    let n = stops.len();
+    let i = stops.iter().position(|&(_, ref id)| *id == current).unwrap();
+    let next = if forward { (i + 1) % n } else { (i + n - 1) % n };
+
+    Some(stops.swap_remove(next).1)
+}
+
+// Labels every view in `info`, using the full area of `printer`.
+fn draw_inspector(info: &view::ViewInfo, focused: bool, printer: &Printer) {
+    let label = |node: &view::ViewInfo| match node.id {
+        Some(ref id) => format!("{} #{}", node.name, id),
+        None => node.name.clone(),
+    };
+
+    // Wrappers using the same area as their child share its label.
+    let mut node = info;
+    let mut focused = focused;
+    let mut text = label(node);
+    while node.children.len() == 1 &&
+          node.children[0].offset == Vec2::zero() &&
+          node.children[0].size == node.size {
+        node = &node.children[0];
+        focused = focused && node.focused;
+        text.push_str(" > ");
+        text.push_str(&label(node));
+    }
+    text.push_str(&format!(" {}x{}", node.size.x, node.size.y));
+
+    let style = if focused {
+        ColorStyle::Highlight
+    } else {
+        ColorStyle::HighlightInactive
+    };
+    printer.with_color(style, |printer| printer.print((0, 0), &text));
+
+    for child in &node.children {
+        let printer = printer.sub_printer(child.offset, child.size, true);
+        draw_inspector(child, focused && child.focused, &printer);
+    }
+}
+
+// Returns the last modification time of a file, if available.
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
 impl Drop for Cursive {
     fn drop(&mut self) {
         self.backend.finish();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use view::Identifiable;
+    use views::{EditView, LinearLayout, StackView};
+    use views::tests::FocusProbe;
+
+    fn content(stack: &mut StackView) -> Option<String> {
+        stack.find_id("edit", |view: &mut EditView| {
+            view.get_content().to_string()
+        })
+    }
+
+    #[test]
+    fn test_binding() {
+        let name = Observable::new("a");
+        let mut binding = Binding {
+            observable: name.clone(),
+            target: "edit".to_string(),
+            applied: None,
+            f: |view: &mut EditView, name: &&str| view.set_content(*name),
+            _view: PhantomData,
+        };
+        let mut stack = StackView::new();
+
+        // The view doesn't exist yet.
+        binding.update(&mut stack);
+        stack.add_layer(EditView::new().with_id("edit"));
+        binding.update(&mut stack);
+        assert_eq!(content(&mut stack), Some("a".to_string()));
+
+        name.set("b");
+        binding.update(&mut stack);
+        assert_eq!(content(&mut stack), Some("b".to_string()));
+
+        // A view added back with the same id is updated, even if the value
+        // didn't change.
+        stack.pop_layer();
+        binding.update(&mut stack);
+        stack.add_layer(EditView::new().content("c").with_id("edit"));
+        binding.update(&mut stack);
+        assert_eq!(content(&mut stack), Some("b".to_string()));
+    }
+
+    #[test]
+    fn test_tab_order() {
+        let mut stack = StackView::new()
+            .layer(EditView::new().with_id("hidden"))
+            .layer(LinearLayout::vertical()
+                       .child(EditView::new().with_id("a"))
+                       .child(EditView::new().with_id("b"))
+                       .child(EditView::new().with_id("c"))
+                       .child(EditView::new().with_id("d")));
+        stack.layout(Vec2::new(80, 24));
+
+        let mut indices = HashMap::new();
+        indices.insert("a".to_string(), 3);
+        indices.insert("c".to_string(), 1);
+        indices.insert("d".to_string(), 2);
+        indices.insert("hidden".to_string(), 0);
+        let step = |stack: &mut StackView, forward: bool| {
+            let id = next_tab_stop(&indices, stack, forward);
+            if let Some(ref id) = id {
+                stack.focus_view(&view::Selector::Id(id)).unwrap();
+            }
+            id
+        };
+
+        // Indices are followed in order, wrapping around, and views from
+        // other layers are skipped.
+        assert_eq!(stack.focused_id(), Some("a".to_string()));
+        assert_eq!(step(&mut stack, true), Some("c".to_string()));
+        assert_eq!(step(&mut stack, true), Some("d".to_string()));
+        assert_eq!(step(&mut stack, true), Some("a".to_string()));
+        assert_eq!(step(&mut stack, false), Some("d".to_string()));
+        assert_eq!(step(&mut stack, false), Some("c".to_string()));
+        assert_eq!(step(&mut stack, false), Some("a".to_string()));
+
+        // Views without an index use the default traversal.
+        let b = view::Selector::Id("b");
+        stack.focus_view(&b).unwrap();
+        assert_eq!(step(&mut stack, true), None);
+    }
+
+    #[test]
+    fn test_move_focus() {
+        let probe = FocusProbe::new();
+        let mut stack = StackView::new()
+            .layer(EditView::new().with_id("hidden"))
+            .layer(LinearLayout::vertical()
+                       .child(probe.clone())
+                       .child(EditView::new().disabled().with_id("disabled"))
+                       .child(EditView::new().with_id("enabled")));
+        stack.layout(Vec2::new(80, 24));
+
+        // A disabled tab stop keeps the focus where it was.
+        let disabled = view::Selector::Id("disabled");
+        assert!(move_focus_to(&mut stack, &disabled).is_err());
+        assert_eq!(probe.lost(), 0);
+
+        // The focus doesn't leave the top layer.
+        let hidden = view::Selector::Id("hidden");
+        assert!(move_focus_to(&mut stack, &hidden).is_ok());
+        assert_eq!(probe.lost(), 0);
+
+        let enabled = view::Selector::Id("enabled");
+        assert!(move_focus_to(&mut stack, &enabled).is_ok());
+        assert_eq!(probe.lost(), 1);
+        assert_eq!(stack.focused_id(), Some("enabled".to_string()));
+
+        let path = view::ViewPath::from(&[1, 0][..]);
+        assert!(move_focus_to(&mut stack, &view::Selector::Path(&path))
+                    .is_ok());
+        assert_eq!(probe.gained(), 1);
+    }
+}
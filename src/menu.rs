@@ -4,17 +4,23 @@
 //!
 //! A menu can be seen as a `MenuTree`. It has a list of children:
 //!
-//! * Leaf nodes are made of a label and a callback
+//! * Leaf nodes are made of a label, a callback and an optional shortcut.
+//!   They can be disabled, or act as a checkbox or a radio button.
 //! * Sub-trees are made of a label, and another `MenuTree`.
 //! * Delimiters are just there to separate groups of related children.
 //!
 //! The [menubar] is the main way to show menus.
 //!
+//! Shortcuts are shown next to their leaf, and the [`Cursive`] root
+//! triggers them when no view consumed the event.
+//!
 //! [menubar]: ../struct.Cursive.html#method.menubar
+//! [`Cursive`]: ../struct.Cursive.html
 
 use Cursive;
 use With;
-use event::Callback;
+use event::{Callback, Event};
+use std::cell::Cell;
 use std::rc::Rc;
 
 /// Root of a menu tree.
@@ -25,16 +31,114 @@ pub struct MenuTree {
 }
 
 /// Node in the menu tree.
+///
+/// Leaves are usually built with the `MenuTree` methods, which fill in
+/// their state.
 #[derive(Clone)]
 pub enum MenuItem {
     /// Actionnable button with a label.
-    Leaf(String, Callback),
+    Leaf {
+        /// Text shown in the menu.
+        label: String,
+        /// Function to run when the leaf is activated.
+        ///
+        /// Use `MenuItem::activate` to also update the checked state.
+        cb: Callback,
+        /// Key combination that activates this leaf from anywhere.
+        shortcut: Option<Event>,
+        /// A disabled leaf cannot be selected or activated.
+        enabled: bool,
+        /// Checked state, for checkbox and radio leaves.
+        checkable: Option<Checkable>,
+    },
     /// Sub-menu with a label.
     Subtree(String, Rc<MenuTree>),
     /// Delimiter without a label.
     Delimiter,
 }
 
+/// Checked state of a menu leaf.
+#[derive(Clone)]
+pub enum Checkable {
+    /// An independent on/off state.
+    Checkbox(Rc<Cell<bool>>),
+    /// One choice in a radio group, with its id in the group.
+    Radio(MenuRadioGroup, usize),
+}
+
+impl Checkable {
+    /// Returns `true` if this checkbox is checked, or this choice is
+    /// the selected one in its group.
+    pub fn is_checked(&self) -> bool {
+        match *self {
+            Checkable::Checkbox(ref state) => state.get(),
+            Checkable::Radio(ref group, id) => group.selected_id() == id,
+        }
+    }
+}
+
+/// Group of radio leaves, with a single active choice.
+///
+/// Leaves are given ids in the order they are added to the group,
+/// starting from 0. The first one is selected by default.
+///
+/// A `MenuRadioGroup` can be cloned; it will keep pointing to the same
+/// group.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cursive::menu::{MenuRadioGroup, MenuTree};
+/// let sort = MenuRadioGroup::new();
+/// let tree = MenuTree::new()
+///     .radio_leaf("Sort by name", &sort, |_| ())
+///     .radio_leaf("Sort by date", &sort, |_| ());
+///
+/// // Later, in a callback...
+/// if sort.selected_id() == 1 {
+///     // Sort by date
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct MenuRadioGroup {
+    selection: Rc<Cell<usize>>,
+    len: Rc<Cell<usize>>,
+}
+
+impl MenuRadioGroup {
+    /// Creates a new, empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of the selected choice.
+    pub fn selected_id(&self) -> usize {
+        self.selection.get()
+    }
+
+    /// Selects the choice with the given id.
+    pub fn set_selected_id(&self, id: usize) {
+        self.selection.set(id);
+    }
+
+    /// Returns the number of choices in this group.
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    /// Returns `true` if this group has no choice yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Reserves an id for a new choice.
+    fn next_id(&self) -> usize {
+        let id = self.len.get();
+        self.len.set(id + 1);
+        id
+    }
+}
+
 impl MenuItem {
     /// Returns the label for this item.
     ///
@@ -42,11 +146,108 @@ impl MenuItem {
     pub fn label(&self) -> &str {
         match *self {
             MenuItem::Delimiter => "",
-            MenuItem::Leaf(ref label, _) |
+            MenuItem::Leaf { ref label, .. } |
             MenuItem::Subtree(ref label, _) => label,
         }
     }
 
+    /// Returns the shortcut for this item, if any.
+    ///
+    /// Only leaves can have a shortcut.
+    pub fn shortcut(&self) -> Option<&Event> {
+        match *self {
+            MenuItem::Leaf { ref shortcut, .. } => shortcut.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Sets the shortcut for this item.
+    ///
+    /// Does nothing if `self` is not a leaf.
+    pub fn set_shortcut(&mut self, event: Option<Event>) {
+        if let MenuItem::Leaf { ref mut shortcut, .. } = *self {
+            *shortcut = event;
+        }
+    }
+
+    /// Returns `true` unless `self` is a disabled leaf.
+    pub fn is_enabled(&self) -> bool {
+        match *self {
+            MenuItem::Leaf { enabled, .. } => enabled,
+            _ => true,
+        }
+    }
+
+    /// Enables or disables this item.
+    ///
+    /// Does nothing if `self` is not a leaf.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if let MenuItem::Leaf { enabled: ref mut e, .. } = *self {
+            *e = enabled;
+        }
+    }
+
+    /// Returns `true` if `self` can be focused in a menu.
+    ///
+    /// Delimiters and disabled leaves cannot.
+    pub fn is_selectable(&self) -> bool {
+        !self.is_delimiter() && self.is_enabled()
+    }
+
+    /// Returns the checked state of this item.
+    ///
+    /// Returns `None` if `self` is not a checkbox or radio leaf.
+    pub fn is_checked(&self) -> Option<bool> {
+        match *self {
+            MenuItem::Leaf { checkable: Some(ref checkable), .. } => {
+                Some(checkable.is_checked())
+            }
+            _ => None,
+        }
+    }
+
+    /// Sets the checked state of this item, without running its callback.
+    ///
+    /// * On a checkbox leaf, checks or unchecks it.
+    /// * On a radio leaf, `true` makes it the selected choice of its group;
+    ///   `false` does nothing.
+    /// * On any other item, does nothing.
+    pub fn set_checked(&mut self, checked: bool) {
+        match *self {
+            MenuItem::Leaf {
+                checkable: Some(Checkable::Checkbox(ref state)), ..
+            } => state.set(checked),
+            MenuItem::Leaf {
+                checkable: Some(Checkable::Radio(ref group, id)), ..
+            } if checked => group.set_selected_id(id),
+            _ => (),
+        }
+    }
+
+    /// Activates this item, as if it was picked in a menu.
+    ///
+    /// Toggles a checkbox leaf, or selects a radio leaf in its group, then
+    /// returns the callback to run.
+    ///
+    /// Returns `None` if `self` is not an enabled leaf.
+    pub fn activate(&self) -> Option<Callback> {
+        match *self {
+            MenuItem::Leaf { ref cb, enabled: true, ref checkable, .. } => {
+                match *checkable {
+                    Some(Checkable::Checkbox(ref state)) => {
+                        state.set(!state.get())
+                    }
+                    Some(Checkable::Radio(ref group, id)) => {
+                        group.set_selected_id(id)
+                    }
+                    None => (),
+                }
+                Some(cb.clone())
+            }
+            _ => None,
+        }
+    }
+
     /// Returns `true` if `self` is a delimiter.
     pub fn is_delimiter(&self) -> bool {
         match *self {
@@ -55,6 +256,14 @@ impl MenuItem {
         }
     }
 
+    /// Returns `true` if `self` is a leaf.
+    pub fn is_leaf(&self) -> bool {
+        match *self {
+            MenuItem::Leaf { .. } => true,
+            _ => false,
+        }
+    }
+
     /// Returns `true` if `self` is a subtree.
     pub fn is_subtree(&self) -> bool {
         match *self {
@@ -106,7 +315,124 @@ impl MenuTree {
               F: 'static + Fn(&mut Cursive)
     {
         let title = title.into();
-        self.children.insert(i, MenuItem::Leaf(title, Callback::from_fn(cb)));
+        self.children.insert(i,
+                             MenuItem::Leaf {
+                                 label: title,
+                                 cb: Callback::from_fn(cb),
+                                 shortcut: None,
+                                 enabled: true,
+                                 checkable: None,
+                             });
+    }
+
+    /// Inserts a checkbox leaf at the given position.
+    ///
+    /// Activating the leaf toggles its state, then calls `cb`
+    /// with the new state.
+    pub fn insert_checkbox_leaf<S, F>(&mut self, i: usize, title: S,
+                                      checked: bool, cb: F)
+        where S: Into<String>,
+              F: 'static + Fn(&mut Cursive, bool)
+    {
+        let state = Rc::new(Cell::new(checked));
+        let checkable = Checkable::Checkbox(state.clone());
+        self.insert_leaf(i, title, move |s| cb(s, state.get()));
+        self.set_checkable(i, checkable);
+    }
+
+    /// Adds a checkbox leaf to the end of this tree.
+    pub fn add_checkbox_leaf<S, F>(&mut self, title: S, checked: bool, cb: F)
+        where S: Into<String>,
+              F: 'static + Fn(&mut Cursive, bool)
+    {
+        let i = self.children.len();
+        self.insert_checkbox_leaf(i, title, checked, cb);
+    }
+
+    /// Adds a checkbox leaf to the end of this tree - chainable variant.
+    pub fn checkbox_leaf<S, F>(self, title: S, checked: bool, cb: F) -> Self
+        where S: Into<String>,
+              F: 'static + Fn(&mut Cursive, bool)
+    {
+        self.with(|menu| menu.add_checkbox_leaf(title, checked, cb))
+    }
+
+    /// Inserts a radio leaf at the given position.
+    ///
+    /// The leaf is added as the next choice in `group`.
+    /// Activating it makes it the selected choice, then calls `cb`.
+    pub fn insert_radio_leaf<S, F>(&mut self, i: usize, title: S,
+                                   group: &MenuRadioGroup, cb: F)
+        where S: Into<String>,
+              F: 'static + Fn(&mut Cursive)
+    {
+        let id = group.next_id();
+        self.insert_leaf(i, title, cb);
+        self.set_checkable(i, Checkable::Radio(group.clone(), id));
+    }
+
+    /// Adds a radio leaf to the end of this tree.
+    pub fn add_radio_leaf<S, F>(&mut self, title: S, group: &MenuRadioGroup,
+                                cb: F)
+        where S: Into<String>,
+              F: 'static + Fn(&mut Cursive)
+    {
+        let i = self.children.len();
+        self.insert_radio_leaf(i, title, group, cb);
+    }
+
+    /// Adds a radio leaf to the end of this tree - chainable variant.
+    pub fn radio_leaf<S, F>(self, title: S, group: &MenuRadioGroup, cb: F)
+                            -> Self
+        where S: Into<String>,
+              F: 'static + Fn(&mut Cursive)
+    {
+        self.with(|menu| menu.add_radio_leaf(title, group, cb))
+    }
+
+    fn set_checkable(&mut self, i: usize, value: Checkable) {
+        if let MenuItem::Leaf { ref mut checkable, .. } = self.children[i] {
+            *checkable = Some(value);
+        }
+    }
+
+    /// Returns `true` if any direct child is a checkbox or radio leaf.
+    pub fn has_checkables(&self) -> bool {
+        self.children.iter().any(|child| child.is_checked().is_some())
+    }
+
+    /// Inserts a leaf with a shortcut at the given position.
+    pub fn insert_shortcut_leaf<S, E, F>(&mut self, i: usize, title: S,
+                                         shortcut: E, cb: F)
+        where S: Into<String>,
+              E: Into<Event>,
+              F: 'static + Fn(&mut Cursive)
+    {
+        self.insert_leaf(i, title, cb);
+        self.children[i].set_shortcut(Some(shortcut.into()));
+    }
+
+    /// Adds a leaf with a shortcut to the end of this tree.
+    ///
+    /// The shortcut is shown next to the label, and triggers the callback
+    /// when no view consumes it.
+    pub fn add_shortcut_leaf<S, E, F>(&mut self, title: S, shortcut: E, cb: F)
+        where S: Into<String>,
+              E: Into<Event>,
+              F: 'static + Fn(&mut Cursive)
+    {
+        let i = self.children.len();
+        self.insert_shortcut_leaf(i, title, shortcut, cb);
+    }
+
+    /// Adds a leaf with a shortcut to the end of this tree - chainable
+    /// variant.
+    pub fn shortcut_leaf<S, E, F>(self, title: S, shortcut: E, cb: F) -> Self
+        where S: Into<String>,
+              E: Into<Event>,
+              F: 'static + Fn(&mut Cursive)
+    {
+        self.with(|menu| menu.add_shortcut_leaf(title, shortcut, cb))
     }
 
 
@@ -174,6 +500,28 @@ impl MenuTree {
             })
     }
 
+    /// Looks for a leaf with the given shortcut, in this tree or a subtree.
+    ///
+    /// Returns a callback activating the first enabled match.
+    pub fn find_shortcut(&self, event: &Event) -> Option<Callback> {
+        self.children.iter().filter_map(|child| match *child {
+            MenuItem::Leaf {
+                shortcut: Some(ref shortcut),
+                enabled: true,
+                ..
+            } if shortcut == event => {
+                let item = child.clone();
+                Some(Callback::from_fn(move |s| {
+                    if let Some(cb) = item.activate() {
+                        cb(s);
+                    }
+                }))
+            }
+            MenuItem::Subtree(_, ref tree) => tree.find_shortcut(event),
+            _ => None,
+        }).next()
+    }
+
     /// Removes the item at the given position.
     pub fn remove(&mut self, i: usize) {
         self.children.remove(i);
@@ -192,3 +540,62 @@ impl MenuTree {
         self.children.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event::Key;
+
+    fn checked(tree: &MenuTree) -> Vec<Option<bool>> {
+        tree.children.iter().map(MenuItem::is_checked).collect()
+    }
+
+    #[test]
+    fn test_checkbox_activate() {
+        let tree = MenuTree::new().checkbox_leaf("Wrap", false, |_, _| ());
+
+        assert!(tree.children[0].activate().is_some());
+        assert_eq!(checked(&tree), vec![Some(true)]);
+        assert!(tree.children[0].activate().is_some());
+        assert_eq!(checked(&tree), vec![Some(false)]);
+    }
+
+    #[test]
+    fn test_radio_activate() {
+        let group = MenuRadioGroup::new();
+        let tree = MenuTree::new()
+            .radio_leaf("Name", &group, |_| ())
+            .radio_leaf("Date", &group, |_| ())
+            .radio_leaf("Size", &group, |_| ());
+        assert_eq!(checked(&tree), vec![Some(true), Some(false), Some(false)]);
+
+        tree.children[2].activate();
+        assert_eq!(checked(&tree), vec![Some(false), Some(false), Some(true)]);
+        tree.children[1].activate();
+        assert_eq!(checked(&tree), vec![Some(false), Some(true), Some(false)]);
+        tree.children[1].activate();
+        assert_eq!(checked(&tree), vec![Some(false), Some(true), Some(false)]);
+    }
+
+    #[test]
+    fn test_disabled_leaf() {
+        let mut sub = MenuTree::new().shortcut_leaf("Save", Key::F2, |_| ());
+        let mut tree = MenuTree::new()
+            .checkbox_leaf("Wrap", false, |_, _| ())
+            .shortcut_leaf("Quit", Key::F10, |_| ());
+
+        assert!(sub.find_shortcut(&Event::Key(Key::F2)).is_some());
+        sub.children[0].set_enabled(false);
+        tree.add_subtree("File", sub);
+        tree.children[0].set_enabled(false);
+        tree.children[1].set_enabled(false);
+
+        assert!(tree.children[0].activate().is_none());
+        assert_eq!(tree.children[0].is_checked(), Some(false));
+        assert!(tree.find_shortcut(&Event::Key(Key::F10)).is_none());
+        assert!(tree.find_shortcut(&Event::Key(Key::F2)).is_none());
+
+        tree.children[1].set_enabled(true);
+        assert!(tree.find_shortcut(&Event::Key(Key::F10)).is_some());
+    }
+}
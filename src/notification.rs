@@ -0,0 +1,198 @@
+//! Transient messages shown on top of the current screen.
+//!
+//! Unlike a [`Dialog`], notifications never take the focus: they are drawn
+//! as small toasts stacked in a corner of the screen, and disappear on
+//! their own after a while.
+//!
+//! Post them with [`Cursive::notify`], from a callback or from another
+//! thread through [`Cursive::cb_sink`].
+//!
+//! [`Dialog`]: ../views/struct.Dialog.html
+//! [`Cursive::notify`]: ../struct.Cursive.html#method.notify
+//! [`Cursive::cb_sink`]: ../struct.Cursive.html#method.cb_sink
+
+use Printer;
+use With;
+use std::cmp::{max, min};
+use std::time::Duration;
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use utils::LinesIterator;
+use vec::Vec2;
+use view::View;
+
+/// Importance of a notification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Simple information.
+    Info,
+    /// Something may need the user's attention.
+    Warning,
+    /// Something went wrong.
+    Error,
+}
+
+impl Severity {
+    /// Returns the label shown in the toast border.
+    pub fn label(&self) -> &'static str {
+        match *self {
+            Severity::Info => "Info",
+            Severity::Warning => "Warning",
+            Severity::Error => "Error",
+        }
+    }
+
+    fn color_style(&self) -> ColorStyle {
+        match *self {
+            Severity::Info => ColorStyle::Secondary,
+            Severity::Warning => ColorStyle::TitleSecondary,
+            Severity::Error => ColorStyle::TitlePrimary,
+        }
+    }
+}
+
+/// A message to show as a toast.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate cursive;
+/// # use cursive::Cursive;
+/// # use cursive::notification::Notification;
+/// # use std::time::Duration;
+/// # fn main() {
+/// let mut siv = Cursive::new();
+///
+/// siv.notify("Saved!");
+/// siv.notify(Notification::error("Disk full")
+///                .duration(Duration::from_secs(10)));
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Notification {
+    message: String,
+    severity: Severity,
+    duration: Duration,
+}
+
+/// Maximum width of a toast, borders included.
+const MAX_WIDTH: usize = 40;
+
+impl Notification {
+    /// Creates a new notification with the given severity.
+    ///
+    /// It will be shown for 3 seconds.
+    pub fn new<S: Into<String>>(message: S, severity: Severity) -> Self {
+        Notification {
+            message: message.into(),
+            severity: severity,
+            duration: Duration::from_secs(3),
+        }
+    }
+
+    /// Creates a new `Info` notification.
+    pub fn info<S: Into<String>>(message: S) -> Self {
+        Notification::new(message, Severity::Info)
+    }
+
+    /// Creates a new `Warning` notification.
+    pub fn warning<S: Into<String>>(message: S) -> Self {
+        Notification::new(message, Severity::Warning)
+    }
+
+    /// Creates a new `Error` notification.
+    pub fn error<S: Into<String>>(message: S) -> Self {
+        Notification::new(message, Severity::Error)
+    }
+
+    /// Sets how long the notification stays on screen.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    /// Sets how long the notification stays on screen.
+    ///
+    /// Chainable variant.
+    pub fn duration(self, duration: Duration) -> Self {
+        self.with(|s| s.set_duration(duration))
+    }
+
+    /// Returns how long the notification stays on screen.
+    pub fn get_duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the message of this notification.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the severity of this notification.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+impl<S: Into<String>> From<S> for Notification {
+    fn from(message: S) -> Self {
+        Notification::info(message)
+    }
+}
+
+impl View for Notification {
+    fn draw(&self, printer: &Printer) {
+        if printer.size.x < 4 || printer.size.y < 3 {
+            return;
+        }
+
+        // Toasts are drawn over other views: clear what's below first.
+        for y in 1..printer.size.y - 1 {
+            printer.print_hline((1, y), printer.size.x - 2, " ");
+        }
+        printer.print_box((0, 0), printer.size, false);
+
+        let label = self.severity.label();
+        if label.width() + 4 <= printer.size.x {
+            printer.with_color(self.severity.color_style(), |printer| {
+                printer.print((2, 0), label);
+            });
+        }
+
+        let width = printer.size.x - 4;
+        let height = printer.size.y - 2;
+        for (y, row) in LinesIterator::new(&self.message, width)
+            .take(height)
+            .enumerate() {
+            printer.print((2, y + 1), &self.message[row.start..row.end]);
+        }
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let width = min(constraint.x, MAX_WIDTH);
+        if width < 5 {
+            return Vec2::new(width, 3);
+        }
+
+        let rows: Vec<_> =
+            LinesIterator::new(&self.message, width - 4).collect();
+        let text_width = rows.iter().map(|row| row.width).max().unwrap_or(0);
+        let label_width = self.severity.label().width();
+
+        Vec2::new(4 + max(text_width, label_width), 2 + rows.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_size() {
+        let mut toast = Notification::info("Hello");
+        assert_eq!(toast.required_size(Vec2::new(80, 24)), Vec2::new(9, 3));
+
+        // Long messages are wrapped.
+        let mut toast = Notification::error("a b c d e f g h i j k l m n o");
+        assert_eq!(toast.required_size(Vec2::new(10, 24)), Vec2::new(10, 7));
+    }
+}
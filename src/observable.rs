@@ -0,0 +1,131 @@
+//! Shared values that keep views in sync.
+//!
+//! An [`Observable`] is a value shared by several parts of the application.
+//! Views can be bound to it with [`Cursive::bind`]: every time the value
+//! changes, the bound views are updated on the next frame.
+//!
+//! Observables can also be stored on the [`Cursive`] root under a name, to
+//! be retrieved from any callback.
+//!
+//! Unlike [`Counter`], observables cannot be shared between threads; use
+//! [`Cursive::cb_sink`] to update them from another thread.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # extern crate cursive;
+//! # use cursive::Cursive;
+//! # use cursive::traits::*;
+//! # use cursive::views::{LinearLayout, SliderView, TextView};
+//! # fn main() {
+//! let mut siv = Cursive::new();
+//!
+//! let volume = siv.add_observable("volume", 5);
+//! siv.bind(&volume, "label", |view: &mut TextView, value: &usize| {
+//!     view.set_content(format!("Volume: {}", value));
+//! });
+//!
+//! siv.add_layer(LinearLayout::vertical()
+//!     .child(TextView::empty().with_id("label"))
+//!     .child(SliderView::horizontal(10)
+//!         .value(5)
+//!         .on_change(|s, value| {
+//!             s.observable::<usize>("volume").unwrap().set(value);
+//!         })));
+//! # }
+//! ```
+//!
+//! [`Observable`]: struct.Observable.html
+//! [`Cursive`]: ../struct.Cursive.html
+//! [`Cursive::bind`]: ../struct.Cursive.html#method.bind
+//! [`Cursive::cb_sink`]: ../struct.Cursive.html#method.cb_sink
+//! [`Counter`]: ../views/struct.Counter.html
+
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+
+/// Value shared between callbacks and views.
+///
+/// Cloning an `Observable` gives another handle to the same value.
+pub struct Observable<T> {
+    inner: Rc<RefCell<Inner<T>>>,
+}
+
+struct Inner<T> {
+    value: T,
+    // Incremented on every change.
+    version: usize,
+}
+
+impl<T> Clone for Observable<T> {
+    fn clone(&self) -> Self {
+        Observable { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Observable<T> {
+    /// Creates a new observable with the given initial value.
+    pub fn new(value: T) -> Self {
+        Observable {
+            inner: Rc::new(RefCell::new(Inner {
+                                            value: value,
+                                            version: 0,
+                                        })),
+        }
+    }
+
+    /// Returns a copy of the current value.
+    pub fn get(&self) -> T
+        where T: Clone
+    {
+        self.inner.borrow().value.clone()
+    }
+
+    /// Returns a reference to the current value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is being modified by `update`.
+    pub fn borrow(&self) -> Ref<T> {
+        Ref::map(self.inner.borrow(), |inner| &inner.value)
+    }
+
+    /// Replaces the value, and marks bound views for update.
+    pub fn set(&self, value: T) {
+        self.update(|v| *v = value);
+    }
+
+    /// Modifies the value in place, and marks bound views for update.
+    pub fn update<F>(&self, f: F)
+        where F: FnOnce(&mut T)
+    {
+        let mut inner = self.inner.borrow_mut();
+        f(&mut inner.value);
+        inner.version += 1;
+    }
+
+    /// Returns the number of times this value was changed.
+    ///
+    /// Bindings compare it to the last version they applied.
+    pub fn version(&self) -> usize {
+        self.inner.borrow().version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observable() {
+        let counter = Observable::new(1);
+        let other = counter.clone();
+        assert_eq!(counter.version(), 0);
+
+        other.set(2);
+        counter.update(|v| *v += 1);
+        assert_eq!(counter.get(), 3);
+        assert_eq!(*other.borrow(), 3);
+        assert_eq!(other.version(), 2);
+    }
+}
@@ -53,7 +53,8 @@ impl<'a> Printer<'a> {
     ///
     /// Users rarely need to call this directly.
     pub fn clear(&self) {
-        self.backend.clear(self.theme.colors.background);
+        let colors = self.backend.color_count();
+        self.backend.clear(self.theme.colors.background.nearest(colors));
     }
 
     /// Returns `true` if nothing has been printed yet.
@@ -129,10 +130,23 @@ impl<'a> Printer<'a> {
     ///     printer.print((0,0), "This text is highlighted!");
     /// });
     /// ```
+    ///
+    /// Colors are degraded to what the terminal can show. Without at least
+    /// 8 colors, `ColorStyle::monochrome_effect()` is applied instead.
     pub fn with_color<F>(&self, c: ColorStyle, f: F)
         where F: FnOnce(&Printer)
     {
-        self.backend.with_color(c.resolve(self.theme), || f(self));
+        let colors = self.backend.color_count();
+        let pair = c.resolve(self.theme).nearest(colors);
+
+        if colors < 8 {
+            let effect = c.monochrome_effect();
+            self.backend.with_color(pair, || {
+                self.backend.with_effect(effect, || f(self))
+            });
+        } else {
+            self.backend.with_color(pair, || f(self));
+        }
     }
 
     /// Same as `with_color`, but apply a ncurses style instead,
@@ -163,6 +177,17 @@ impl<'a> Printer<'a> {
     /// ```
     pub fn print_box<T: Into<Vec2>, S: Into<Vec2>>(&self, start: T, size: S,
                                                    invert: bool) {
+        self.print_styled_box(start, size, invert, &self.theme.borders);
+    }
+
+    /// Prints a rectangular box with the given border style.
+    ///
+    /// Same as `print_box`, ignoring the theme's border style.
+    pub fn print_styled_box<T, S>(&self, start: T, size: S, invert: bool,
+                                  style: &BorderStyle)
+        where T: Into<Vec2>,
+              S: Into<Vec2>
+    {
         self.new.set(false);
 
         let start = start.into();
@@ -172,19 +197,31 @@ impl<'a> Printer<'a> {
             return;
         }
         let size = size - (1, 1);
-
-        self.with_high_border(invert, |s| {
-            s.print(start, "┌");
-            s.print(start + size.keep_y(), "└");
-            s.print_hline(start + (1, 0), size.x - 1, "─");
-            s.print_vline(start + (0, 1), size.y - 1, "│");
+        let chars = style.chars();
+        let mut buf = [0; 4];
+
+        self.with_styled_high_border(style, invert, |s| {
+            s.print(start, chars.top_left.encode_utf8(&mut buf));
+            s.print(start + size.keep_y(),
+                    chars.bottom_left.encode_utf8(&mut buf));
+            s.print_hline(start + (1, 0),
+                          size.x - 1,
+                          chars.horizontal.encode_utf8(&mut buf));
+            s.print_vline(start + (0, 1),
+                          size.y - 1,
+                          chars.vertical.encode_utf8(&mut buf));
         });
 
-        self.with_low_border(invert, |s| {
-            s.print(start + size.keep_x(), "┐");
-            s.print(start + size, "┘");
-            s.print_hline(start + (1, 0) + size.keep_y(), size.x - 1, "─");
-            s.print_vline(start + (0, 1) + size.keep_x(), size.y - 1, "│");
+        self.with_styled_low_border(style, invert, |s| {
+            s.print(start + size.keep_x(),
+                    chars.top_right.encode_utf8(&mut buf));
+            s.print(start + size, chars.bottom_right.encode_utf8(&mut buf));
+            s.print_hline(start + (1, 0) + size.keep_y(),
+                          size.x - 1,
+                          chars.horizontal.encode_utf8(&mut buf));
+            s.print_vline(start + (0, 1) + size.keep_x(),
+                          size.y - 1,
+                          chars.vertical.encode_utf8(&mut buf));
         });
     }
 
@@ -197,13 +234,7 @@ impl<'a> Printer<'a> {
     pub fn with_high_border<F>(&self, invert: bool, f: F)
         where F: FnOnce(&Printer)
     {
-        let color = match self.theme.borders {
-            BorderStyle::None => return,
-            BorderStyle::Outset if !invert => ColorStyle::Tertiary,
-            _ => ColorStyle::Primary,
-        };
-
-        self.with_color(color, f);
+        self.with_styled_high_border(&self.theme.borders, invert, f);
     }
 
     /// Runs the given function using a color depending on the theme.
@@ -215,7 +246,29 @@ impl<'a> Printer<'a> {
     pub fn with_low_border<F>(&self, invert: bool, f: F)
         where F: FnOnce(&Printer)
     {
-        let color = match self.theme.borders {
+        self.with_styled_low_border(&self.theme.borders, invert, f);
+    }
+
+    /// Same as `with_high_border`, ignoring the theme's border style.
+    pub fn with_styled_high_border<F>(&self, style: &BorderStyle,
+                                      invert: bool, f: F)
+        where F: FnOnce(&Printer)
+    {
+        let color = match *style {
+            BorderStyle::None => return,
+            BorderStyle::Outset if !invert => ColorStyle::Tertiary,
+            _ => ColorStyle::Primary,
+        };
+
+        self.with_color(color, f);
+    }
+
+    /// Same as `with_low_border`, ignoring the theme's border style.
+    pub fn with_styled_low_border<F>(&self, style: &BorderStyle,
+                                     invert: bool, f: F)
+        where F: FnOnce(&Printer)
+    {
+        let color = match *style {
             BorderStyle::None => return,
             BorderStyle::Outset if invert => ColorStyle::Tertiary,
             _ => ColorStyle::Primary,
@@ -245,11 +298,32 @@ impl<'a> Printer<'a> {
     }
 
     /// Prints a horizontal delimiter with side border `├` and `┤`.
+    ///
+    /// Characters depend on the theme's border style.
     pub fn print_hdelim<T: Into<Vec2>>(&self, start: T, len: usize) {
         let start = start.into();
-        self.print(start, "├");
-        self.print_hline(start + (1, 0), len.saturating_sub(2), "─");
-        self.print(start + (len.saturating_sub(1), 0), "┤");
+        let chars = self.theme.borders.chars();
+        let mut buf = [0; 4];
+        self.print(start, chars.left_tee.encode_utf8(&mut buf));
+        self.print_hline(start + (1, 0),
+                         len.saturating_sub(2),
+                         chars.horizontal.encode_utf8(&mut buf));
+        self.print(start + (len.saturating_sub(1), 0),
+                   chars.right_tee.encode_utf8(&mut buf));
+    }
+
+    /// Returns a printer on the same area, using the given theme.
+    ///
+    /// Views drawn with it, and their children, will use this theme.
+    pub fn with_theme<'b>(&'b self, theme: &'b Theme) -> Printer<'b> {
+        Printer {
+            offset: self.offset,
+            size: self.size,
+            focused: self.focused,
+            theme: theme,
+            backend: self.backend,
+            new: self.new.clone(),
+        }
     }
 
     /// Returns a printer on a subset of this one's area.
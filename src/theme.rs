@@ -29,6 +29,11 @@
 //! * **`highlight_inactive`**: used to highlight selected but inactive items.
 //!   Defaults to **blue**.
 //!
+//! Themes can also define other colors, with any name. Applications refer
+//! to them with [`ColorStyle::Named`].
+//!
+//! [`ColorStyle::Named`]: enum.ColorStyle.html#variant.Named
+//!
 //! # Color Styles
 //!
 //! Each cell of the terminal uses two colors: *foreground* and *background*.
@@ -65,6 +70,8 @@
 //!   but inactive items.
 //!     * Its *background* color is `highlight_inactive`.
 //!     * Its *foreground* color is `view`.
+//! * **`Named`**: style using palette entries from their name.
+//!     * When an entry is missing, the color from a fallback style is used.
 //!
 //! Using one of these pairs when styling your application helps give it a
 //! coherent look.
@@ -88,7 +95,9 @@
 //!
 //! # First come some various options
 //! shadow = false  # Don't draw shadows around stacked views
-//! borders = "simple"  # Alternatives are "none" and "outset"
+//! # Alternatives are "none", "outset", "double", "rounded", "heavy"
+//! # and "ascii". A `[borders]` table can also set custom characters.
+//! borders = "simple"
 //!
 //! # Here we define the color palette.
 //! [colors]
@@ -112,23 +121,65 @@
 //! 	# Lower precision values can use only 3 digits.
 //! 	highlight          = "#F00"
 //! 	highlight_inactive = "#5555FF"
+//!
+//! 	# Other names define application-specific colors.
+//! 	error = "light red"
+//! ```
+//!
+//! # Presets
+//!
+//! A few themes are built in, and can be selected by name with [`preset`]:
+//!
+//! * **`default`**: white views on a blue background.
+//! * **`light`**: light grey views on a white background.
+//! * **`dark`**: dark grey views on a black background.
+//! * **`high_contrast`**: bright text on black, for accessibility.
+//! * **`monochrome`**: only black and shades of white, for terminals
+//!   without colors.
+//!
+//! A theme file can also start from a preset, and only change some entries:
+//!
+//! ```toml
+//! preset = "dark"
+//!
+//! [colors]
+//! 	highlight = "light magenta"
 //! ```
+//!
+//! Unknown entries and invalid colors are silently ignored by [`load_theme`].
+//! Use [`load_theme_strict`] to get a list of these entries instead.
+//!
+//! A theme can be written back to the same format with [`Theme::to_toml`]
+//! or [`save_theme_file`].
+//!
+//! [`preset`]: fn.preset.html
+//! [`load_theme`]: fn.load_theme.html
+//! [`load_theme_strict`]: fn.load_theme_strict.html
+//! [`Theme::to_toml`]: struct.Theme.html#method.to_toml
+//! [`save_theme_file`]: fn.save_theme_file.html
 
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write as FmtWrite;
 use std::fs::File;
 use std::io;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 
 use toml;
 
 /// Text effect
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Effect {
     /// No effect
     Simple,
     /// Reverses foreground and background colors
     Reverse,
-    // TODO: bold, italic, underline
+    /// Underlines the text
+    Underline,
+    /// Prints the text in bold (or with a brighter color)
+    Bold,
+    // TODO: italic
 }
 
 /// Combines a front and back color.
@@ -158,6 +209,16 @@ impl ColorPair {
             back: Color::from_256colors(back),
         }
     }
+
+    /// Returns the closest pair a terminal with `colors` colors can show.
+    ///
+    /// See [`Color::nearest`](enum.Color.html#method.nearest).
+    pub fn nearest(&self, colors: u32) -> Self {
+        ColorPair {
+            front: self.front.nearest(colors),
+            back: self.back.nearest(colors),
+        }
+    }
 }
 
 /// Possible color style for a cell.
@@ -194,6 +255,35 @@ pub enum ColorStyle {
         /// Background color
         back: Color,
     },
+    /// Uses palette entries from their name.
+    ///
+    /// Names can refer to the built-in entries (like `"view"`), or to any
+    /// color defined by the theme.
+    ///
+    /// Since `ColorStyle` is `Copy`, names must be `'static` strings.
+    /// For a name only known at runtime, look the colors up with
+    /// `Palette::get` and use `ColorStyle::Custom` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cursive::theme::ColorStyle;
+    /// // Uses the `error` color from the theme if it has one,
+    /// // or the `TitlePrimary` front color otherwise.
+    /// const ERROR: ColorStyle = ColorStyle::Named {
+    ///     front: "error",
+    ///     back: "view",
+    ///     fallback: &ColorStyle::TitlePrimary,
+    /// };
+    /// ```
+    Named {
+        /// Name of the foreground color
+        front: &'static str,
+        /// Name of the background color
+        back: &'static str,
+        /// Style giving the colors missing from the palette
+        fallback: &'static ColorStyle,
+    },
 }
 
 impl ColorStyle {
@@ -217,13 +307,37 @@ impl ColorStyle {
             ColorStyle::Highlight => (c.view, c.highlight),
             ColorStyle::HighlightInactive => (c.view, c.highlight_inactive),
             ColorStyle::Custom { front, back } => (front, back),
+            ColorStyle::Named {
+                front,
+                back,
+                fallback,
+            } => {
+                let fallback = fallback.resolve(theme);
+                (c.get(front).unwrap_or(fallback.front),
+                 c.get(back).unwrap_or(fallback.back))
+            }
         };
         ColorPair { front, back }
     }
+
+    /// Returns the effect used for this style when colors are unavailable.
+    ///
+    /// This keeps highlighted elements and titles recognizable on
+    /// monochrome terminals.
+    pub fn monochrome_effect(&self) -> Effect {
+        match *self {
+            ColorStyle::Highlight => Effect::Reverse,
+            ColorStyle::HighlightInactive => Effect::Underline,
+            ColorStyle::TitlePrimary |
+            ColorStyle::TitleSecondary => Effect::Bold,
+            ColorStyle::Named { fallback, .. } => fallback.monochrome_effect(),
+            _ => Effect::Simple,
+        }
+    }
 }
 
 /// Represents the style a Cursive application will use.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Theme {
     /// Whether views in a StackView should have shadows.
     pub shadow: bool,
@@ -249,27 +363,97 @@ impl Default for Theme {
                 title_secondary: Color::Dark(BaseColor::Yellow),
                 highlight: Color::Dark(BaseColor::Red),
                 highlight_inactive: Color::Dark(BaseColor::Blue),
+                named: BTreeMap::new(),
             },
         }
     }
 }
 
 impl Theme {
-    fn load(&mut self, table: &toml::value::Table) {
-        if let Some(&toml::Value::Boolean(shadow)) = table.get("shadow") {
-            self.shadow = shadow;
+    /// Returns the toml representation of this theme.
+    ///
+    /// It can be loaded back with [`load_theme`].
+    ///
+    /// [`load_theme`]: fn.load_theme.html
+    pub fn to_toml(&self) -> String {
+        let mut result = format!("shadow = {}\n", self.shadow);
+        match self.borders {
+            BorderStyle::Custom(chars) => {
+                write!(result, "\n[borders]\n{}", chars.to_toml()).unwrap()
+            }
+            borders => writeln!(result, "borders = \"{}\"", borders).unwrap(),
         }
+        write!(result, "\n[colors]\n{}", self.colors.to_toml()).unwrap();
+        result
+    }
 
-        if let Some(&toml::Value::String(ref borders)) = table.get("borders") {
-            self.borders = BorderStyle::from(borders);
+    // Invalid entries are added to `errors`, and otherwise ignored.
+    fn load(&mut self, table: &toml::value::Table,
+            errors: &mut Vec<InvalidEntry>) {
+        // The preset is the base for all other entries.
+        if let Some(&toml::Value::String(ref name)) = table.get("preset") {
+            if let Some(theme) = preset(name) {
+                *self = theme;
+            }
         }
 
-        if let Some(&toml::Value::Table(ref table)) = table.get("colors") {
-            self.colors.load(table);
+        for (key, value) in table {
+            let valid = match (key.as_str(), value) {
+                ("preset", &toml::Value::String(ref name)) => {
+                    PRESETS.contains(&name.as_str())
+                }
+                ("shadow", &toml::Value::Boolean(shadow)) => {
+                    self.shadow = shadow;
+                    true
+                }
+                ("borders", &toml::Value::String(ref borders)) => {
+                    self.borders = BorderStyle::from(borders);
+                    BorderStyle::parse(borders).is_some()
+                }
+                ("borders", &toml::Value::Table(ref table)) => {
+                    let mut chars = self.borders.chars();
+                    chars.load(table, errors);
+                    self.borders = BorderStyle::Custom(chars);
+                    true
+                }
+                ("colors", &toml::Value::Table(ref table)) => {
+                    self.colors.load(table, errors);
+                    true
+                }
+                _ => false,
+            };
+
+            if !valid {
+                errors.push(InvalidEntry::new(key, value));
+            }
         }
     }
 }
 
+/// Entry from a theme file that could not be used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidEntry {
+    /// Full key of the entry, like `colors.view`.
+    pub key: String,
+    /// Value of the entry, in toml format.
+    pub value: String,
+}
+
+impl InvalidEntry {
+    fn new(key: &str, value: &toml::Value) -> Self {
+        InvalidEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for InvalidEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.key, self.value)
+    }
+}
+
 /// Specifies how some borders should be drawn.
 ///
 /// Borders are used around Dialogs, select popups, and panels.
@@ -281,24 +465,189 @@ pub enum BorderStyle {
     Outset,
     /// No borders.
     None,
+    /// Double-line borders.
+    Double,
+    /// Simple borders with rounded corners.
+    Rounded,
+    /// Thick borders.
+    Heavy,
+    /// Borders using only ASCII characters.
+    ///
+    /// Works on any terminal, even without box-drawing glyphs.
+    Ascii,
+    /// Borders using the given characters.
+    Custom(BorderChars),
 }
 
 impl BorderStyle {
     fn from(s: &str) -> Self {
-        if s == "simple" {
-            BorderStyle::Simple
-        } else if s == "outset" {
-            BorderStyle::Outset
-        } else {
-            BorderStyle::None
+        BorderStyle::parse(s).unwrap_or(BorderStyle::None)
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "simple" => Some(BorderStyle::Simple),
+            "outset" => Some(BorderStyle::Outset),
+            "none" => Some(BorderStyle::None),
+            "double" => Some(BorderStyle::Double),
+            "rounded" => Some(BorderStyle::Rounded),
+            "heavy" => Some(BorderStyle::Heavy),
+            "ascii" => Some(BorderStyle::Ascii),
+            _ => None,
+        }
+    }
+
+    /// Returns the characters used to draw this style.
+    ///
+    /// `None` and `Outset` use the same characters as `Simple`.
+    pub fn chars(&self) -> BorderChars {
+        let (corners, lines, tees) = match *self {
+            BorderStyle::Simple | BorderStyle::Outset | BorderStyle::None => {
+                ("┌┐└┘", "─│", "├┤")
+            }
+            BorderStyle::Double => ("╔╗╚╝", "═║", "╠╣"),
+            BorderStyle::Rounded => ("╭╮╰╯", "─│", "├┤"),
+            BorderStyle::Heavy => ("┏┓┗┛", "━┃", "┣┫"),
+            BorderStyle::Ascii => ("++++", "-|", "++"),
+            BorderStyle::Custom(chars) => return chars,
+        };
+
+        let mut corners = corners.chars();
+        let mut lines = lines.chars();
+        let mut tees = tees.chars();
+        BorderChars {
+            top_left: corners.next().unwrap(),
+            top_right: corners.next().unwrap(),
+            bottom_left: corners.next().unwrap(),
+            bottom_right: corners.next().unwrap(),
+            horizontal: lines.next().unwrap(),
+            vertical: lines.next().unwrap(),
+            left_tee: tees.next().unwrap(),
+            right_tee: tees.next().unwrap(),
+        }
+    }
+}
+
+/// Custom borders are written as `custom`.
+///
+/// Their characters are saved in a `[borders]` table by `Theme::to_toml`.
+impl fmt::Display for BorderStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+                        BorderStyle::Simple => "simple",
+                        BorderStyle::Outset => "outset",
+                        BorderStyle::None => "none",
+                        BorderStyle::Double => "double",
+                        BorderStyle::Rounded => "rounded",
+                        BorderStyle::Heavy => "heavy",
+                        BorderStyle::Ascii => "ascii",
+                        BorderStyle::Custom(_) => "custom",
+                    })
+    }
+}
+
+/// Characters used to draw borders.
+///
+/// In a theme file, a `[borders]` table sets custom characters.
+/// Missing entries keep the simple style:
+///
+/// ```toml
+/// [borders]
+/// 	top_left = "*"
+/// 	top_right = "*"
+/// 	bottom_left = "*"
+/// 	bottom_right = "*"
+/// 	horizontal = "="
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BorderChars {
+    /// Top-left corner.
+    pub top_left: char,
+    /// Top-right corner.
+    pub top_right: char,
+    /// Bottom-left corner.
+    pub bottom_left: char,
+    /// Bottom-right corner.
+    pub bottom_right: char,
+    /// Horizontal lines.
+    pub horizontal: char,
+    /// Vertical lines.
+    pub vertical: char,
+    /// Junction of a horizontal line with a left border, like `├`.
+    pub left_tee: char,
+    /// Junction of a horizontal line with a right border, like `┤`.
+    pub right_tee: char,
+}
+
+impl BorderChars {
+    /// Returns the toml representation of these characters.
+    ///
+    /// This is the content of the `[borders]` table in a theme file.
+    pub fn to_toml(&self) -> String {
+        let mut result = String::new();
+        for &(key, c) in &self.entries() {
+            writeln!(result,
+                     "{} = {}",
+                     key,
+                     toml::Value::String(c.to_string()))
+                    .unwrap();
+        }
+        result
+    }
+
+    // Invalid entries are added to `errors`, and otherwise ignored.
+    fn load(&mut self, table: &toml::value::Table,
+            errors: &mut Vec<InvalidEntry>) {
+        for (key, value) in table {
+            let c = match *value {
+                toml::Value::String(ref s) if s.chars().count() == 1 => {
+                    s.chars().next()
+                }
+                _ => None,
+            };
+
+            match (self.char_mut(key), c) {
+                (Some(target), Some(c)) => *target = c,
+                _ => {
+                    errors.push(InvalidEntry::new(&format!("borders.{}", key),
+                                                  value))
+                }
+            }
         }
     }
+
+    fn entries(&self) -> [(&'static str, char); 8] {
+        [("top_left", self.top_left),
+         ("top_right", self.top_right),
+         ("bottom_left", self.bottom_left),
+         ("bottom_right", self.bottom_right),
+         ("horizontal", self.horizontal),
+         ("vertical", self.vertical),
+         ("left_tee", self.left_tee),
+         ("right_tee", self.right_tee)]
+    }
+
+    fn char_mut(&mut self, key: &str) -> Option<&mut char> {
+        Some(match key {
+                 "top_left" => &mut self.top_left,
+                 "top_right" => &mut self.top_right,
+                 "bottom_left" => &mut self.bottom_left,
+                 "bottom_right" => &mut self.bottom_right,
+                 "horizontal" => &mut self.horizontal,
+                 "vertical" => &mut self.vertical,
+                 "left_tee" => &mut self.left_tee,
+                 "right_tee" => &mut self.right_tee,
+                 _ => return None,
+             })
+    }
 }
 
 /// Color configuration for the application.
 ///
 /// Assign each color role an actual color.
-#[derive(Copy, Clone, Debug)]
+///
+/// On top of the built-in roles, it can hold any number of named colors.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Palette {
     /// Color used for the application background.
     pub background: Color,
@@ -320,24 +669,119 @@ pub struct Palette {
     pub highlight: Color,
     /// Color used for highlighting inactive text.
     pub highlight_inactive: Color,
+    // Application-specific colors.
+    named: BTreeMap<String, Color>,
 }
 
 impl Palette {
+    /// Returns the toml representation of this palette.
+    ///
+    /// This is the content of the `[colors]` table in a theme file.
+    pub fn to_toml(&self) -> String {
+        let mut result = String::new();
+        for &(key, color) in &self.entries() {
+            writeln!(result, "{} = \"{}\"", key, color).unwrap();
+        }
+        for (key, color) in &self.named {
+            writeln!(result, "{} = \"{}\"", toml_key(key), color).unwrap();
+        }
+        result
+    }
+
+    /// Returns the color with the given name, if any.
+    ///
+    /// This can be a built-in entry, like `"view"`, or a named color.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.entries()
+            .iter()
+            .find(|&&(key, _)| key == name)
+            .map(|&(_, color)| color)
+            .or_else(|| self.named.get(name).cloned())
+    }
+
+    /// Sets the color with the given name.
+    ///
+    /// This can be a built-in entry, like `"view"`, or a named color.
+    pub fn set(&mut self, name: &str, color: Color) {
+        match self.color_mut(name) {
+            Some(target) => *target = color,
+            None => {
+                self.named.insert(name.to_string(), color);
+            }
+        }
+    }
+
+    /// Removes a named color, and returns it.
+    ///
+    /// Built-in entries cannot be removed.
+    pub fn remove(&mut self, name: &str) -> Option<Color> {
+        self.named.remove(name)
+    }
+
     /// Fills `self` with the colors from the given `table`.
-    fn load(&mut self, table: &toml::value::Table) {
-        load_color(&mut self.background, table.get("background"));
-        load_color(&mut self.shadow, table.get("shadow"));
-        load_color(&mut self.view, table.get("view"));
-        load_color(&mut self.primary, table.get("primary"));
-        load_color(&mut self.secondary, table.get("secondary"));
-        load_color(&mut self.tertiary, table.get("tertiary"));
-        load_color(&mut self.title_primary, table.get("title_primary"));
-        load_color(&mut self.title_secondary, table.get("title_secondary"));
-        load_color(&mut self.highlight, table.get("highlight"));
-        load_color(
-            &mut self.highlight_inactive,
-            table.get("highlight_inactive"),
-        );
+    ///
+    /// Invalid entries are added to `errors`, and otherwise ignored.
+    fn load(&mut self, table: &toml::value::Table,
+            errors: &mut Vec<InvalidEntry>) {
+        for (key, value) in table {
+            let valid = match self.color_mut(key) {
+                Some(color) => load_color(color, Some(value)),
+                None => {
+                    let mut color = Color::TerminalDefault;
+                    let valid = load_color(&mut color, Some(value));
+                    if valid {
+                        self.named.insert(key.to_string(), color);
+                    }
+                    valid
+                }
+            };
+
+            if !valid {
+                errors.push(InvalidEntry::new(&format!("colors.{}", key),
+                                              value));
+            }
+        }
+    }
+
+    fn entries(&self) -> [(&'static str, Color); 10] {
+        [("background", self.background),
+         ("shadow", self.shadow),
+         ("view", self.view),
+         ("primary", self.primary),
+         ("secondary", self.secondary),
+         ("tertiary", self.tertiary),
+         ("title_primary", self.title_primary),
+         ("title_secondary", self.title_secondary),
+         ("highlight", self.highlight),
+         ("highlight_inactive", self.highlight_inactive)]
+    }
+
+    fn color_mut(&mut self, key: &str) -> Option<&mut Color> {
+        Some(match key {
+                 "background" => &mut self.background,
+                 "shadow" => &mut self.shadow,
+                 "view" => &mut self.view,
+                 "primary" => &mut self.primary,
+                 "secondary" => &mut self.secondary,
+                 "tertiary" => &mut self.tertiary,
+                 "title_primary" => &mut self.title_primary,
+                 "title_secondary" => &mut self.title_secondary,
+                 "highlight" => &mut self.highlight,
+                 "highlight_inactive" => &mut self.highlight_inactive,
+                 _ => return None,
+             })
+    }
+}
+
+/// Returns `key`, quoted if needed.
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty() &&
+               key.chars()
+                   .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
     }
 }
 
@@ -400,6 +844,21 @@ pub enum BaseColor {
     White,
 }
 
+impl BaseColor {
+    fn name(&self) -> &'static str {
+        match *self {
+            BaseColor::Black => "black",
+            BaseColor::Red => "red",
+            BaseColor::Green => "green",
+            BaseColor::Yellow => "yellow",
+            BaseColor::Blue => "blue",
+            BaseColor::Magenta => "magenta",
+            BaseColor::Cyan => "cyan",
+            BaseColor::White => "white",
+        }
+    }
+}
+
 impl From<u8> for BaseColor {
     fn from(n: u8) -> Self {
         match n % 8 {
@@ -435,13 +894,58 @@ pub enum Color {
     RgbLowRes(u8, u8, u8),
 }
 
+// Values used by each channel in the 6x6x6 color cube.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// Dark then light base colors, as defined by xterm.
+const BASE_RGB: [(u8, u8, u8); 16] = [(0, 0, 0),
+                                      (205, 0, 0),
+                                      (0, 205, 0),
+                                      (205, 205, 0),
+                                      (0, 0, 238),
+                                      (205, 0, 205),
+                                      (0, 205, 205),
+                                      (229, 229, 229),
+                                      (127, 127, 127),
+                                      (255, 0, 0),
+                                      (0, 255, 0),
+                                      (255, 255, 0),
+                                      (92, 92, 255),
+                                      (255, 0, 255),
+                                      (0, 255, 255),
+                                      (255, 255, 255)];
+
+// Index of the closest cube level for the given channel value.
+fn cube_level(value: u8) -> u8 {
+    (0..6)
+        .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+        .unwrap() as u8
+}
+
+/// Writes the color in the format used by theme files.
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Color::TerminalDefault => write!(f, "default"),
+            Color::Dark(base) => write!(f, "{}", base.name()),
+            Color::Light(base) => write!(f, "light {}", base.name()),
+            Color::Rgb(r, g, b) => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            Color::RgbLowRes(r, g, b) => write!(f, "{}{}{}", r, g, b),
+        }
+    }
+}
+
 /// Possible error returned when loading a theme.
 #[derive(Debug)]
 pub enum Error {
-    /// An error occured when reading the file.
+    /// An error occured when reading or writing the file.
     Io(io::Error),
     /// An error occured when parsing the toml content.
     Parse(toml::de::Error),
+    /// Some entries could not be used.
+    ///
+    /// Only returned by strict loading functions.
+    Invalid(Vec<InvalidEntry>),
 }
 
 impl From<io::Error> for Error {
@@ -478,6 +982,56 @@ impl Color {
         }
     }
 
+    /// Returns the closest color a terminal with `colors` colors can show.
+    ///
+    /// * With true-color support (`1 << 24`), colors are unchanged.
+    /// * With 256 colors, `Rgb` colors are mapped to the 6x6x6 color cube.
+    /// * With 16 or 8 colors, the closest base color is used
+    ///   (with 8 colors, light colors use their dark version).
+    /// * With fewer colors, everything becomes `TerminalDefault`.
+    pub fn nearest(&self, colors: u32) -> Self {
+        match *self {
+            Color::TerminalDefault => Color::TerminalDefault,
+            _ if colors < 8 => Color::TerminalDefault,
+            _ if colors >= 1 << 24 => *self,
+            Color::Rgb(r, g, b) if colors >= 256 => {
+                Color::RgbLowRes(cube_level(r), cube_level(g), cube_level(b))
+            }
+            Color::RgbLowRes(_, _, _) if colors >= 256 => *self,
+            Color::Light(_) if colors >= 16 => *self,
+            Color::Light(base) => Color::Dark(base),
+            Color::Dark(_) => *self,
+            _ => {
+                let (r, g, b) = self.to_rgb();
+                let count = if colors >= 16 { 16 } else { 8 };
+                let distance = |n: &u8| {
+                    let (r2, g2, b2) = Color::from_256colors(*n).to_rgb();
+                    let dr = r as i32 - r2 as i32;
+                    let dg = g as i32 - g2 as i32;
+                    let db = b as i32 - b2 as i32;
+                    dr * dr + dg * dg + db * db
+                };
+                let n = (0..count).min_by_key(distance).unwrap();
+                Color::from_256colors(n)
+            }
+        }
+    }
+
+    // Approximate RGB value, as shown by xterm.
+    fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::TerminalDefault => (0, 0, 0),
+            Color::Dark(base) => BASE_RGB[base as usize],
+            Color::Light(base) => BASE_RGB[8 + base as usize],
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::RgbLowRes(r, g, b) => {
+                (CUBE_LEVELS[r as usize],
+                 CUBE_LEVELS[g as usize],
+                 CUBE_LEVELS[b as usize])
+            }
+        }
+    }
+
     fn parse(value: &str) -> Option<Self> {
         Some(match value {
             "black" => Color::Dark(BaseColor::Black),
@@ -505,11 +1059,15 @@ impl Color {
         if value.starts_with('#') {
 
             let value = &value[1..];
+            if !value.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+
             // Compute per-color length, and amplitude
             let (l, multiplier) = match value.len() {
                 6 => (2, 1),
                 3 => (1, 17),
-                _ => panic!("Cannot parse color: {}", value),
+                _ => return None,
             };
             let r = load_hex(&value[0..l]) * multiplier;
             let g = load_hex(&value[l..2 * l]) * multiplier;
@@ -532,16 +1090,16 @@ impl Color {
     }
 }
 
+fn read_file<P: AsRef<Path>>(filename: P) -> Result<String, Error> {
+    let mut content = String::new();
+    let mut file = try!(File::open(filename));
+    try!(file.read_to_string(&mut content));
+    Ok(content)
+}
+
 /// Loads a theme from file and sets it as active.
 pub fn load_theme_file<P: AsRef<Path>>(filename: P) -> Result<Theme, Error> {
-    let content = {
-        let mut content = String::new();
-        let mut file = try!(File::open(filename));
-        try!(file.read_to_string(&mut content));
-        content
-    };
-
-    load_theme(&content)
+    load_theme(&try!(read_file(filename)))
 }
 
 /// Loads a theme string and sets it as active.
@@ -549,11 +1107,142 @@ pub fn load_theme(content: &str) -> Result<Theme, Error> {
     let table = toml::de::from_str(content)?;
 
     let mut theme = Theme::default();
-    theme.load(&table);
+    theme.load(&table, &mut Vec::new());
 
     Ok(theme)
 }
 
+/// Names of the built-in themes.
+///
+/// See [`preset`].
+///
+/// [`preset`]: fn.preset.html
+pub const PRESETS: &'static [&'static str] =
+    &["default", "light", "dark", "high_contrast", "monochrome"];
+
+/// Returns the built-in theme with the given name.
+///
+/// Returns `None` if `name` is not in [`PRESETS`].
+///
+/// [`PRESETS`]: constant.PRESETS.html
+pub fn preset(name: &str) -> Option<Theme> {
+    let content = match name {
+        "default" => return Some(Theme::default()),
+        "light" => LIGHT,
+        "dark" => DARK,
+        "high_contrast" => HIGH_CONTRAST,
+        "monochrome" => MONOCHROME,
+        _ => return None,
+    };
+
+    load_theme(content).ok()
+}
+
+const LIGHT: &'static str = r##"
+shadow = true
+borders = "simple"
+
+[colors]
+background = "light white"
+shadow = "light black"
+view = "white"
+primary = "black"
+secondary = "blue"
+tertiary = "light black"
+title_primary = "blue"
+title_secondary = "magenta"
+highlight = "blue"
+highlight_inactive = "light black"
+"##;
+
+const DARK: &'static str = r##"
+shadow = false
+borders = "simple"
+
+[colors]
+background = "black"
+shadow = "black"
+view = "light black"
+primary = "light white"
+secondary = "light cyan"
+tertiary = "white"
+title_primary = "light yellow"
+title_secondary = "light cyan"
+highlight = "cyan"
+highlight_inactive = "blue"
+"##;
+
+const HIGH_CONTRAST: &'static str = r##"
+shadow = false
+borders = "simple"
+
+[colors]
+background = "black"
+shadow = "black"
+view = "black"
+primary = "light white"
+secondary = "light yellow"
+tertiary = "light white"
+title_primary = "light yellow"
+title_secondary = "light cyan"
+highlight = "light yellow"
+highlight_inactive = "light white"
+"##;
+
+const MONOCHROME: &'static str = r##"
+shadow = false
+borders = "simple"
+
+[colors]
+background = "black"
+shadow = "black"
+view = "black"
+primary = "white"
+secondary = "white"
+tertiary = "white"
+title_primary = "white"
+title_secondary = "white"
+highlight = "light white"
+highlight_inactive = "white"
+"##;
+
+/// Loads a theme from file, failing on any invalid entry.
+///
+/// See [`load_theme_strict`].
+///
+/// [`load_theme_strict`]: fn.load_theme_strict.html
+pub fn load_theme_file_strict<P: AsRef<Path>>(filename: P)
+                                              -> Result<Theme, Error> {
+    load_theme_strict(&try!(read_file(filename)))
+}
+
+/// Loads a theme string, failing on any invalid entry.
+///
+/// Unknown keys, values of the wrong type and colors that cannot be parsed
+/// are all reported in an `Error::Invalid`.
+/// An array of colors is valid if at least one of them can be parsed.
+pub fn load_theme_strict(content: &str) -> Result<Theme, Error> {
+    let table = toml::de::from_str(content)?;
+
+    let mut theme = Theme::default();
+    let mut errors = Vec::new();
+    theme.load(&table, &mut errors);
+
+    if errors.is_empty() {
+        Ok(theme)
+    } else {
+        Err(Error::Invalid(errors))
+    }
+}
+
+/// Saves a theme to the given file, in toml format.
+pub fn save_theme_file<P: AsRef<Path>>(theme: &Theme, filename: P)
+                                       -> Result<(), Error> {
+    let mut file = try!(File::create(filename));
+    try!(file.write_all(theme.to_toml().as_bytes()));
+    Ok(())
+}
+
 /// Loads the default theme, and returns its representation.
 pub fn load_default() -> Theme {
     Theme::default()
@@ -574,3 +1263,135 @@ fn load_hex(s: &str) -> u16 {
 
     sum as u16
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut theme = Theme {
+            shadow: false,
+            borders: BorderStyle::Outset,
+            ..Theme::default()
+        };
+        theme.colors.view = Color::Rgb(0x12, 0xab, 0x0f);
+        theme.colors.primary = Color::Light(BaseColor::Cyan);
+        theme.colors.secondary = Color::RgbLowRes(5, 4, 1);
+        theme.colors.tertiary = Color::TerminalDefault;
+        theme.colors.set("error", Color::Light(BaseColor::Red));
+        theme.colors.set("needs quotes", Color::Dark(BaseColor::Green));
+
+        let loaded = load_theme_strict(&theme.to_toml()).unwrap();
+        assert_eq!(loaded, theme);
+    }
+
+    #[test]
+    fn test_strict() {
+        let content = r##"
+            shadow = "yes"
+            borders = "outset"
+            frobnicate = true
+
+            [colors]
+            view = ["#12", "blu"]
+            primary = ["BLUE", "yellow"]
+            highligt = "rde"
+        "##;
+
+        // Lenient loading keeps what it can.
+        let theme = load_theme(content).unwrap();
+        assert_eq!(theme.borders, BorderStyle::Outset);
+        assert_eq!(theme.colors.primary, Color::Dark(BaseColor::Yellow));
+
+        let mut keys: Vec<String> = match load_theme_strict(content) {
+            Err(Error::Invalid(errors)) => {
+                errors.into_iter().map(|e| e.to_string()).collect()
+            }
+            _ => panic!("strict loading should fail"),
+        };
+        keys.sort();
+        assert_eq!(keys,
+                   vec!["colors.highligt = \"rde\"",
+                        "colors.view = [\"#12\", \"blu\"]",
+                        "frobnicate = true",
+                        "shadow = \"yes\""]);
+    }
+
+    #[test]
+    fn test_presets() {
+        for name in PRESETS {
+            let theme = preset(name).unwrap();
+            assert_eq!(load_theme_strict(&theme.to_toml()).unwrap(), theme);
+            // The focused selection must stand out from inactive ones.
+            assert_ne!(theme.colors.highlight,
+                       theme.colors.highlight_inactive);
+        }
+        assert!(preset("solarized").is_none());
+
+        let content = "preset = \"dark\"\n[colors]\nview = \"red\"";
+        let theme = load_theme_strict(content).unwrap();
+        assert!(!theme.shadow);
+        assert_eq!(theme.colors.view, Color::Dark(BaseColor::Red));
+        assert_eq!(theme.colors.background, Color::Dark(BaseColor::Black));
+    }
+
+    #[test]
+    fn test_nearest() {
+        let red = Color::Rgb(255, 64, 0);
+        assert_eq!(red.nearest(1 << 24), red);
+        assert_eq!(red.nearest(256), Color::RgbLowRes(5, 1, 0));
+        assert_eq!(red.nearest(16), Color::Light(BaseColor::Red));
+        assert_eq!(red.nearest(8), Color::Dark(BaseColor::Red));
+        assert_eq!(red.nearest(0), Color::TerminalDefault);
+
+        let grey = Color::Light(BaseColor::Black);
+        assert_eq!(grey.nearest(16), grey);
+        assert_eq!(grey.nearest(8), Color::Dark(BaseColor::Black));
+
+        assert_ne!(ColorStyle::Highlight.monochrome_effect(),
+                   ColorStyle::HighlightInactive.monochrome_effect());
+    }
+
+    #[test]
+    fn test_borders() {
+        let mut theme = Theme::default();
+        for name in &["double", "rounded", "heavy", "ascii"] {
+            theme.borders = BorderStyle::parse(name).unwrap();
+            assert_eq!(load_theme_strict(&theme.to_toml()).unwrap(), theme);
+        }
+
+        let content = "[borders]\nhorizontal = \"=\"\nvertical = \"||\"";
+        let theme = load_theme(content).unwrap();
+        let chars = theme.borders.chars();
+        assert_eq!(chars.horizontal, '=');
+        assert_eq!(chars.vertical, '│');
+        assert_eq!(load_theme_strict(&theme.to_toml()).unwrap(), theme);
+
+        match load_theme_strict(content) {
+            Err(Error::Invalid(errors)) => {
+                assert_eq!(errors[0].key, "borders.vertical")
+            }
+            _ => panic!("strict loading should fail"),
+        }
+    }
+
+    #[test]
+    fn test_named() {
+        const ERROR: ColorStyle = ColorStyle::Named {
+            front: "error",
+            back: "view",
+            fallback: &ColorStyle::TitlePrimary,
+        };
+
+        let mut theme = Theme::default();
+        assert_eq!(ERROR.resolve(&theme),
+                   ColorStyle::TitlePrimary.resolve(&theme));
+
+        theme = load_theme("[colors]\nerror = \"light red\"").unwrap();
+        let red = Color::Light(BaseColor::Red);
+        assert_eq!(theme.colors.get("error"), Some(red));
+        assert_eq!(ERROR.resolve(&theme).front, red);
+        assert_eq!(ERROR.resolve(&theme).back, theme.colors.view);
+    }
+}
@@ -100,6 +100,49 @@ pub fn simple_suffix(text: &str, width: usize) -> Prefix {
     suffix(text.graphemes(true), width, "")
 }
 
+/// Scores how well `pattern` fuzzy-matches `text`.
+///
+/// Every character of `pattern` must appear in `text`, in the same order,
+/// ignoring case. Returns `None` otherwise.
+///
+/// Higher scores are better matches: consecutive characters and characters
+/// at the start of a word are worth more.
+///
+/// An empty pattern matches everything with a score of 0.
+pub fn fuzzy_score(pattern: &str, text: &str) -> Option<usize> {
+    let mut pattern = pattern.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut consecutive = false;
+
+    for c in text.chars() {
+        let matched = match pattern.peek() {
+            None => break,
+            Some(&p) => c.to_lowercase().eq(Some(p)),
+        };
+
+        if matched {
+            pattern.next();
+            score += 1;
+            if consecutive {
+                score += 5;
+            }
+            if previous.map_or(true, |p| !p.is_alphanumeric()) {
+                score += 3;
+            }
+        }
+
+        consecutive = matched;
+        previous = Some(c);
+    }
+
+    if pattern.peek().is_some() {
+        None
+    } else {
+        Some(score)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use utils;
@@ -110,4 +153,15 @@ mod tests {
         assert_eq!(utils::prefix("abra a".split(' '), 5, " ").length, 4);
         assert_eq!(utils::prefix("a a br".split(' '), 5, " ").length, 3);
     }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(utils::fuzzy_score("", "File > Open"), Some(0));
+        assert_eq!(utils::fuzzy_score("xyz", "File > Open"), None);
+        assert_eq!(utils::fuzzy_score("nepo", "File > Open"), None);
+        assert!(utils::fuzzy_score("FO", "file > open").is_some());
+        // Consecutive matches beat scattered ones.
+        assert!(utils::fuzzy_score("open", "File > Open") >
+                utils::fuzzy_score("open", "Options > Pen"));
+    }
 }
@@ -0,0 +1,86 @@
+use direction::Direction;
+use event::{Event, EventResult, MouseEvent};
+use vec::Vec2;
+
+/// Helper for views holding several focusable children.
+///
+/// Such containers usually forward events to their focused child. Mouse
+/// events should go to the child under the mouse instead, and clicks move
+/// the focus there.
+///
+/// Implement the required methods, then call [`route_mouse_event`] at the
+/// start of `View::on_event`, and [`change_focus`] whenever the focus
+/// moves, so children receive `Event::FocusLost` and `Event::FocusGained`.
+///
+/// [`route_mouse_event`]: #method.route_mouse_event
+/// [`change_focus`]: #method.change_focus
+pub trait FocusContainer {
+    /// Identifies a child, like its index.
+    type Child: Copy + PartialEq;
+
+    /// Returns the position where this view was last drawn.
+    ///
+    /// Mouse events are reported in absolute coordinates.
+    fn last_offset(&self) -> Vec2;
+
+    /// Returns the child drawn at the given position.
+    ///
+    /// `position` is relative to this view.
+    fn child_at(&self, position: Vec2) -> Option<Self::Child>;
+
+    /// Returns the child currently focused.
+    fn focused_child(&self) -> Self::Child;
+
+    /// Records `child` as the focused one, without notifying anyone.
+    fn set_focused_child(&mut self, child: Self::Child);
+
+    /// Offers the focus to a child. Returns `true` if it takes it.
+    fn child_take_focus(&mut self, child: Self::Child, source: Direction)
+                        -> bool;
+
+    /// Sends an event to a child.
+    fn child_on_event(&mut self, child: Self::Child, event: Event)
+                      -> EventResult;
+
+    /// Moves the focus to `child`, notifying the old and new focused
+    /// children.
+    ///
+    /// `child` should already have accepted the focus.
+    fn change_focus(&mut self, child: Self::Child) -> EventResult {
+        let old = self.focused_child();
+        self.set_focused_child(child);
+
+        let lost = self.child_on_event(old, Event::FocusLost);
+        let gained = self.child_on_event(child, Event::FocusGained);
+        EventResult::Consumed(None).and(lost).and(gained)
+    }
+
+    /// Sends a click or a wheel event to the child under the mouse.
+    ///
+    /// Clicks also give it the focus, if it takes it.
+    ///
+    /// Returns `None` if the focused child should get the event instead.
+    fn route_mouse_event(&mut self, position: Vec2, event: MouseEvent)
+                         -> Option<EventResult> {
+        if !event.is_positional() {
+            return None;
+        }
+        let child = match position.checked_sub(self.last_offset())
+            .and_then(|p| self.child_at(p)) {
+            Some(child) if child != self.focused_child() => child,
+            _ => return None,
+        };
+
+        let focus = if event.grabs_focus() &&
+                       self.child_take_focus(child, Direction::none()) {
+            self.change_focus(child)
+        } else {
+            EventResult::Ignored
+        };
+        let event = Event::Mouse {
+            position: position,
+            event: event,
+        };
+        Some(focus.and(self.child_on_event(child, event)))
+    }
+}
@@ -43,14 +43,17 @@ mod position;
 mod size_cache;
 mod size_constraint;
 mod view_path;
+mod view_info;
 
 // Helper bases
+mod focus_container;
 mod scroll;
 mod identifiable;
 mod boxable;
 
 
 pub use self::boxable::Boxable;
+pub use self::focus_container::FocusContainer;
 pub use self::identifiable::Identifiable;
 
 pub use self::position::{Offset, Position};
@@ -60,6 +63,7 @@ pub use self::scroll::{ScrollBase, ScrollStrategy};
 pub use self::size_cache::SizeCache;
 pub use self::size_constraint::SizeConstraint;
 pub use self::view_path::ViewPath;
+pub use self::view_info::ViewInfo;
 pub use self::view_wrapper::ViewWrapper;
 use Printer;
 
@@ -131,9 +135,16 @@ pub trait View {
     ///
     /// Returns `Ok(())` if the view was found and selected.
     ///
-    /// Default implementation simply returns `Err(())`.
-    fn focus_view(&mut self, &Selector) -> Result<(), ()> {
-        Err(())
+    /// Default implementation takes the focus if the selector is an
+    /// empty path, and returns `Err(())` otherwise.
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        match *selector {
+            Selector::Path(path) if path.path.is_empty() &&
+                                    self.take_focus(Direction::none()) => {
+                Ok(())
+            }
+            _ => Err(()),
+        }
     }
 
     /// This view is offered focus. Will it take it?
@@ -146,6 +157,43 @@ pub trait View {
         let _ = source;
         false
     }
+
+    /// Returns the id of the focused view, if it has one.
+    ///
+    /// This is the innermost view with an id along the focus chain.
+    /// View groups should ask their focused child first.
+    ///
+    /// Default implementation returns `None`.
+    fn focused_id(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the path to the focused view, relative to this one.
+    ///
+    /// View groups should prepend the index of their focused child.
+    ///
+    /// Default implementation returns an empty path.
+    fn focused_path(&self) -> ViewPath {
+        ViewPath::new()
+    }
+
+    /// Returns the id of the view at the given path, if it has one.
+    ///
+    /// Default implementation returns `None`.
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        let _ = path;
+        None
+    }
+
+    /// Describes this view and its children, to debug layouts.
+    ///
+    /// `size` is the size given to this view by its parent.
+    /// View groups should add their children, with their offset.
+    ///
+    /// Default implementation describes a view without children.
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        ViewInfo::new::<Self>(size)
+    }
 }
 
 /// Provides `call_on<V: View>` to views.
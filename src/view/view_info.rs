@@ -0,0 +1,167 @@
+use std::any;
+use std::fmt;
+use vec::Vec2;
+
+/// Description of a view and its children, used to debug layouts.
+///
+/// It is returned by [`View::inspect`].
+///
+/// The `Display` implementation prints the tree as indented text, with the
+/// size and offset of each view. Views on the focus path are marked with
+/// a `*`.
+///
+/// [`View::inspect`]: trait.View.html#method.inspect
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ViewInfo {
+    /// Type of the view, without module paths (like `IdView<EditView>`).
+    pub name: String,
+    /// Id of the view, if it is an `IdView`.
+    pub id: Option<String>,
+    /// Position of the view, relative to its parent.
+    pub offset: Vec2,
+    /// Size given to the view.
+    pub size: Vec2,
+    /// `true` if the parent gives the focus to this view.
+    pub focused: bool,
+    /// Description of the children, in order.
+    pub children: Vec<ViewInfo>,
+}
+
+impl ViewInfo {
+    /// Creates a new description for a view of type `V`, without children.
+    pub fn new<V: ?Sized>(size: Vec2) -> Self {
+        ViewInfo {
+            name: short_type_name(any::type_name::<V>()),
+            id: None,
+            offset: Vec2::zero(),
+            size: size,
+            focused: false,
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds a child, placed at `offset` in this view.
+    pub fn add_child(&mut self, offset: Vec2, focused: bool,
+                     mut child: ViewInfo) {
+        child.offset = offset;
+        child.focused = focused;
+        self.children.push(child);
+    }
+
+    /// Adds a child, placed at `offset` in this view.
+    ///
+    /// Chainable variant.
+    pub fn child(mut self, offset: Vec2, focused: bool, child: ViewInfo)
+                 -> Self {
+        self.add_child(offset, focused, child);
+        self
+    }
+
+    /// Returns the focused child, if any.
+    pub fn focused_child(&self) -> Option<&ViewInfo> {
+        self.children.iter().find(|child| child.focused)
+    }
+
+    /// Returns the position of the innermost focused view, relative to
+    /// this one.
+    pub fn focus_offset(&self) -> Vec2 {
+        match self.focused_child() {
+            Some(child) => child.offset + child.focus_offset(),
+            None => Vec2::zero(),
+        }
+    }
+
+    fn write_tree(&self, f: &mut fmt::Formatter, depth: usize,
+                  on_focus_path: bool)
+                  -> fmt::Result {
+        try!(write!(f, "{:indent$}{}", "", self.name, indent = 2 * depth));
+        if let Some(ref id) = self.id {
+            try!(write!(f, " #{}", id));
+        }
+        try!(write!(f,
+                    " {}x{}+{}+{}",
+                    self.size.x,
+                    self.size.y,
+                    self.offset.x,
+                    self.offset.y));
+        if on_focus_path {
+            try!(write!(f, " *"));
+        }
+        try!(writeln!(f));
+
+        for child in &self.children {
+            let focused = on_focus_path && child.focused;
+            try!(child.write_tree(f, depth + 1, focused));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ViewInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_tree(f, 0, true)
+    }
+}
+
+// Removes module paths from a type name.
+//
+// `cursive::views::IdView<cursive::views::EditView>` becomes
+// `IdView<EditView>`.
+fn short_type_name(name: &str) -> String {
+    let mut result = String::new();
+    let mut word = String::new();
+
+    for c in name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            word.push(c);
+        } else {
+            result.push_str(word.rsplit("::").next().unwrap_or(""));
+            result.push(c);
+            word.clear();
+        }
+    }
+    result.push_str(word.rsplit("::").next().unwrap_or(""));
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name("a::b::Foo<c::Bar, d::e::Baz>"),
+                   "Foo<Bar, Baz>");
+        assert_eq!(short_type_name("alloc::boxed::Box<dyn a::View>"),
+                   "Box<dyn View>");
+    }
+
+    #[test]
+    fn test_display() {
+        struct Leaf;
+
+        let mut info = ViewInfo::new::<Leaf>(Vec2::new(10, 2));
+        info.add_child(Vec2::new(0, 1),
+                       true,
+                       ViewInfo::new::<u8>(Vec2::new(4, 1)));
+        info.add_child(Vec2::new(4, 1),
+                       false,
+                       ViewInfo::new::<u8>(Vec2::new(6, 1)));
+
+        assert_eq!(info.to_string(),
+                   "Leaf 10x2+0+0 *\n  u8 4x1+0+1 *\n  u8 6x1+4+1\n");
+    }
+
+    #[test]
+    fn test_focus_offset() {
+        let leaf = ViewInfo::new::<u8>(Vec2::new(4, 1));
+        let row = ViewInfo::new::<u16>(Vec2::new(8, 1))
+            .child(Vec2::zero(), false, leaf.clone())
+            .child(Vec2::new(4, 0), true, leaf);
+        let info = ViewInfo::new::<u32>(Vec2::new(8, 3))
+            .child(Vec2::new(0, 2), true, row);
+
+        assert_eq!(info.focus_offset(), Vec2::new(4, 2));
+    }
+}
@@ -1,4 +1,9 @@
 /// Represents a path to a single view in the layout.
+///
+/// Each view group uses one turn to pick a child (usually its index), and
+/// passes the rest of the path to it. An empty path designates the view
+/// itself.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ViewPath {
     /// List of turns to make on decision nodes when descending the view tree.
     /// Simple nodes (with one fixed child) are skipped.
@@ -17,6 +22,15 @@ impl ViewPath {
     pub fn from<T: ToPath>(path: T) -> Self {
         path.to_path()
     }
+
+    /// Returns the first turn and the rest of the path.
+    ///
+    /// Returns `None` if the path is empty.
+    pub fn split_first(&self) -> Option<(usize, ViewPath)> {
+        self.path
+            .split_first()
+            .map(|(&first, rest)| (first, ViewPath::from(rest)))
+    }
 }
 
 /// Generic trait for elements that can be converted into a `ViewPath`.
@@ -4,7 +4,7 @@ use direction::Direction;
 use event::{Event, EventResult};
 use std::any::Any;
 use vec::Vec2;
-use view::{Selector, View};
+use view::{Selector, View, ViewInfo, ViewPath};
 
 /// Generic wrapper around a view.
 ///
@@ -81,6 +81,32 @@ pub trait ViewWrapper {
     fn wrap_needs_relayout(&self) -> bool {
         self.with_view(|v| v.needs_relayout()).unwrap_or(true)
     }
+
+    /// Wraps the `focused_id` method.
+    fn wrap_focused_id(&self) -> Option<String> {
+        self.with_view(|v| v.focused_id()).unwrap_or(None)
+    }
+
+    /// Wraps the `focused_path` method.
+    fn wrap_focused_path(&self) -> ViewPath {
+        self.with_view(|v| v.focused_path()).unwrap_or_default()
+    }
+
+    /// Wraps the `id_at` method.
+    fn wrap_id_at(&self, path: &ViewPath) -> Option<String> {
+        self.with_view(|v| v.id_at(path)).unwrap_or(None)
+    }
+
+    /// Wraps the `inspect` method.
+    ///
+    /// The wrapped view is described as a child, with the same size.
+    fn wrap_inspect(&self, size: Vec2) -> ViewInfo {
+        let info = ViewInfo::new::<Self>(size);
+        match self.with_view(|v| v.inspect(size)) {
+            Some(child) => info.child(Vec2::zero(), true, child),
+            None => info,
+        }
+    }
 }
 
 // Some types easily implement ViewWrapper.
@@ -102,6 +128,11 @@ impl<U: View + ?Sized, T: Deref<Target = U> + DerefMut> ViewWrapper for T {
     {
         Some(f(self.deref_mut()))
     }
+
+    // Boxes are not worth showing: describe the inner view directly.
+    fn wrap_inspect(&self, size: Vec2) -> ViewInfo {
+        self.deref().inspect(size)
+    }
 }
 
 // The main point of implementing ViewWrapper is to have View for free.
@@ -137,6 +168,22 @@ impl<T: ViewWrapper> View for T {
     fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
         self.wrap_focus_view(selector)
     }
+
+    fn focused_id(&self) -> Option<String> {
+        self.wrap_focused_id()
+    }
+
+    fn focused_path(&self) -> ViewPath {
+        self.wrap_focused_path()
+    }
+
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        self.wrap_id_at(path)
+    }
+
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        self.wrap_inspect(size)
+    }
 }
 
 /// Convenient macro to implement the [`ViewWrapper`] trait.
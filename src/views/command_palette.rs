@@ -0,0 +1,249 @@
+use Printer;
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key};
+use menu::{MenuItem, MenuTree};
+use std::cmp::min;
+
+use unicode_width::UnicodeWidthStr;
+use utils::fuzzy_score;
+use vec::Vec2;
+use view::{ScrollBase, View};
+use views::EditView;
+
+/// Action that can be launched from a [`CommandPalette`].
+///
+/// [`CommandPalette`]: struct.CommandPalette.html
+#[derive(Clone)]
+pub struct Command {
+    /// Name shown in the palette, and used for the search.
+    pub label: String,
+    /// Key binding that also triggers this command, if any.
+    pub key: Option<Event>,
+    /// Function to run when the command is chosen.
+    pub callback: Callback,
+}
+
+impl Command {
+    /// Creates a new command without key binding.
+    pub fn new<S: Into<String>>(label: S, callback: Callback) -> Self {
+        Command {
+            label: label.into(),
+            key: None,
+            callback: callback,
+        }
+    }
+
+    /// Collects every leaf of the given menu tree as a command.
+    ///
+    /// Labels are the full path to the leaf, starting with `prefix`
+    /// and joined with ` > `, like `File > Recent > Item 1`.
+    pub fn from_menu(prefix: &str, tree: &MenuTree) -> Vec<Command> {
+        let mut commands = Vec::new();
+        collect_leaves(prefix, tree, &mut commands);
+        commands
+    }
+}
+
+fn collect_leaves(prefix: &str, tree: &MenuTree, commands: &mut Vec<Command>) {
+    for item in &tree.children {
+        match *item {
+            MenuItem::Leaf { enabled: false, .. } => (),
+            MenuItem::Leaf { ref label, ref shortcut, .. } => {
+                let label = format!("{} > {}", prefix, label);
+                let item = item.clone();
+                commands.push(Command {
+                    label: label,
+                    key: shortcut.clone(),
+                    callback: Callback::from_fn(move |s| {
+                        if let Some(cb) = item.activate() {
+                            cb(s);
+                        }
+                    }),
+                });
+            }
+            MenuItem::Subtree(ref label, ref tree) => {
+                let prefix = format!("{} > {}", prefix, label);
+                collect_leaves(&prefix, tree, commands);
+            }
+            MenuItem::Delimiter => (),
+        }
+    }
+}
+
+/// Searchable list of commands, like a Ctrl+P launcher.
+///
+/// The top row is a search field; the list below only shows the commands
+/// fuzzy-matching the query, best match first.
+///
+/// * `<Up>`/`<Down>` move in the list.
+/// * `<Enter>` closes the palette and runs the selected command.
+/// * `<Esc>` closes the palette.
+///
+/// [`Cursive::show_command_palette`] opens a palette with all the menubar
+/// leaves and named global callbacks.
+///
+/// [`Cursive::show_command_palette`]: ../struct.Cursive.html#method.show_command_palette
+pub struct CommandPalette {
+    commands: Vec<Command>,
+    query: EditView,
+    // Indices into `commands` matching the current query, best first.
+    matches: Vec<usize>,
+    focus: usize,
+    scrollbase: ScrollBase,
+}
+
+impl CommandPalette {
+    /// Creates a new palette listing the given commands.
+    pub fn new(commands: Vec<Command>) -> Self {
+        let matches = (0..commands.len()).collect();
+        CommandPalette {
+            commands: commands,
+            query: EditView::new(),
+            matches: matches,
+            focus: 0,
+            scrollbase: ScrollBase::new(),
+        }
+    }
+
+    /// Adds a command to the list.
+    pub fn add_command(&mut self, command: Command) {
+        self.commands.push(command);
+        self.update_matches();
+    }
+
+    /// Returns the current search query.
+    pub fn query(&self) -> String {
+        self.query.get_content().to_string()
+    }
+
+    /// Sets the search query, and filters the list accordingly.
+    pub fn set_query<S: Into<String>>(&mut self, query: S) {
+        self.query.set_content(query);
+        self.update_matches();
+    }
+
+    /// Returns the command currently selected.
+    ///
+    /// Returns `None` if no command matches the query.
+    pub fn selection(&self) -> Option<&Command> {
+        self.matches.get(self.focus).map(|&i| &self.commands[i])
+    }
+
+    fn update_matches(&mut self) {
+        let query = self.query.get_content();
+        let mut scored: Vec<(usize, usize)> = self.commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, command)| {
+                fuzzy_score(&query, &command.label).map(|score| (score, i))
+            })
+            .collect();
+        // Best score first; keep the original order for ties.
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.focus = 0;
+        self.scrollbase.scroll_top();
+    }
+
+    fn key_label(command: &Command) -> String {
+        command.key.as_ref().map(Event::to_string).unwrap_or_default()
+    }
+}
+
+impl View for CommandPalette {
+    fn draw(&self, printer: &Printer) {
+        printer.print((0, 0), "> ");
+        let width = printer.size.x.saturating_sub(2);
+        self.query.draw(&printer.sub_printer((2, 0), (width, 1), true));
+        let chars = printer.theme.borders.chars();
+        let mut buf = [0; 4];
+        let line = chars.horizontal.encode_utf8(&mut buf);
+        printer.print_hline((0, 1), printer.size.x, line);
+
+        let printer = printer.sub_printer((0, 2),
+                                          printer.size.saturating_sub((0, 2)),
+                                          true);
+        self.scrollbase.draw(&printer, |printer, i| {
+            let command = &self.commands[self.matches[i]];
+            let key = Self::key_label(command);
+            printer.with_selection(i == self.focus, |printer| {
+                printer.print_hline((0, 0), printer.size.x, " ");
+                printer.print((1, 0), &command.label);
+                let x = printer.size.x.saturating_sub(key.width() + 1);
+                printer.print((x, 0), &key);
+            });
+        });
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        // One space on each side, and 2 between the label and the key.
+        let w = self.commands
+            .iter()
+            .map(|command| {
+                command.label.width() + Self::key_label(command).width() + 4
+            })
+            .max()
+            .unwrap_or(1);
+        // Keep some room to type, even with short labels.
+        let w = ::std::cmp::max(w, 30);
+
+        let h = 2 + self.commands.len();
+        let w = if req.y < h { w + 2 } else { w };
+
+        Vec2::new(w, h)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.query.layout(Vec2::new(size.x.saturating_sub(2), 1));
+        self.scrollbase
+            .set_heights(size.y.saturating_sub(2), self.matches.len());
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Esc) => {
+                return EventResult::with_cb(|s| s.pop_layer());
+            }
+            Event::Key(Key::Enter) => {
+                return match self.selection() {
+                    Some(command) => {
+                        let cb = command.callback.clone();
+                        EventResult::with_cb(move |s| {
+                            s.pop_layer();
+                            cb(s);
+                        })
+                    }
+                    None => EventResult::Consumed(None),
+                };
+            }
+            Event::Key(Key::Up) => self.focus = self.focus.saturating_sub(1),
+            Event::Key(Key::Down) => {
+                self.focus = min(self.focus + 1,
+                                 self.matches.len().saturating_sub(1))
+            }
+            Event::Key(Key::PageUp) => {
+                self.focus = self.focus.saturating_sub(10)
+            }
+            Event::Key(Key::PageDown) => {
+                self.focus = min(self.focus + 10,
+                                 self.matches.len().saturating_sub(1))
+            }
+            event => {
+                // Everything else goes to the search field.
+                if self.query.on_event(event).is_consumed() {
+                    self.update_matches();
+                    return EventResult::Consumed(None);
+                }
+                return EventResult::Ignored;
+            }
+        }
+
+        self.scrollbase.scroll_to(self.focus);
+        EventResult::Consumed(None)
+    }
+}
@@ -0,0 +1,164 @@
+use Printer;
+use With;
+use event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use menu::MenuTree;
+use std::cell::Cell;
+use std::rc::Rc;
+use vec::Vec2;
+use view::{View, ViewWrapper};
+
+/// Wrapper view that opens a popup menu on demand.
+///
+/// The menu opens on a right-click inside the wrapped view, at the mouse
+/// position, or on `<Menu>` and `<Shift-F10>`, at the focused child.
+/// Other keys can be registered with [`add_trigger`].
+///
+/// Right-clicks are only reported once the mouse is enabled with
+/// [`Cursive::set_mouse_capture`].
+///
+/// The menu is added to the current screen as a new layer, and always
+/// fits inside the screen.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate cursive;
+/// # use cursive::Cursive;
+/// # use cursive::menu::MenuTree;
+/// # use cursive::views::{ContextMenu, Dialog, TextView};
+/// # fn main() {
+/// let mut siv = Cursive::new();
+/// siv.set_mouse_capture(true);
+///
+/// let menu = MenuTree::new()
+///     .leaf("Copy", |s| s.add_layer(Dialog::info("Copied!")))
+///     .leaf("Quit", |s| s.quit());
+///
+/// siv.add_layer(ContextMenu::new(TextView::new("Right-click me!"), menu));
+/// # }
+/// ```
+///
+/// [`add_trigger`]: #method.add_trigger
+/// [`Cursive::set_mouse_capture`]: ../struct.Cursive.html#method.set_mouse_capture
+pub struct ContextMenu<V: View> {
+    view: V,
+    menu: Rc<MenuTree>,
+    triggers: Vec<Event>,
+
+    // Where we were last drawn, to place the menu and locate clicks.
+    last_offset: Cell<Vec2>,
+    last_size: Vec2,
+}
+
+impl<V: View> ContextMenu<V> {
+    /// Wraps `view`, attaching the given menu to it.
+    pub fn new(view: V, menu: MenuTree) -> Self {
+        ContextMenu {
+            view: view,
+            menu: Rc::new(menu),
+            triggers: vec![Event::Key(Key::Menu), Event::Shift(Key::F10)],
+            last_offset: Cell::new(Vec2::zero()),
+            last_size: Vec2::zero(),
+        }
+    }
+
+    /// Adds an event that opens the menu, in addition to `<Menu>` and
+    /// `<Shift-F10>`.
+    pub fn add_trigger<E: Into<Event>>(&mut self, event: E) {
+        self.triggers.push(event.into());
+    }
+
+    /// Adds an event that opens the menu.
+    ///
+    /// Chainable variant.
+    pub fn trigger<E: Into<Event>>(self, event: E) -> Self {
+        self.with(|s| s.add_trigger(event))
+    }
+
+    /// Returns a mutable reference to the menu.
+    ///
+    /// If the menu is currently open, it will keep its previous content.
+    pub fn menu_mut(&mut self) -> &mut MenuTree {
+        Rc::make_mut(&mut self.menu)
+    }
+
+    /// Replaces the menu.
+    pub fn set_menu(&mut self, menu: MenuTree) {
+        self.menu = Rc::new(menu);
+    }
+
+    /// Returns a reference to the wrapped view.
+    pub fn get_view(&self) -> &V {
+        &self.view
+    }
+
+    /// Returns a mutable reference to the wrapped view.
+    pub fn get_view_mut(&mut self) -> &mut V {
+        &mut self.view
+    }
+
+    fn contains(&self, position: Vec2) -> bool {
+        let offset = self.last_offset.get();
+        position.fits(offset) &&
+        (position - offset + (1, 1)).fits_in(self.last_size)
+    }
+
+    // Position of the focused child, where the keyboard opens the menu.
+    fn focus_position(&self) -> Vec2 {
+        let info = self.view.inspect(self.last_size);
+        self.last_offset.get() + info.focus_offset()
+    }
+
+    fn open_at(&self, position: Vec2) -> EventResult {
+        let menu = self.menu.clone();
+        EventResult::with_cb(move |s| s.show_menu_at(position, menu.clone()))
+    }
+}
+
+impl<V: View> ViewWrapper for ContextMenu<V> {
+    wrap_impl!(self.view: V);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
+        self.view.draw(printer);
+    }
+
+    fn wrap_layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        self.view.layout(size);
+    }
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Mouse {
+                position,
+                event: MouseEvent::Press(MouseButton::Right),
+            } if self.contains(position) => self.open_at(position),
+            ref event if self.triggers.contains(event) => {
+                self.open_at(self.focus_position())
+            }
+            event => self.view.on_event(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use direction::Direction;
+    use views::{EditView, LinearLayout};
+
+    #[test]
+    fn test_focus_position() {
+        let layout = LinearLayout::vertical()
+            .child(EditView::new())
+            .child(EditView::new());
+        let mut view = ContextMenu::new(layout, MenuTree::new());
+        view.layout(Vec2::new(10, 2));
+        assert!(view.take_focus(Direction::front()));
+        assert_eq!(view.focus_position(), Vec2::zero());
+
+        view.on_event(Event::Key(Key::Down));
+        assert_eq!(view.focus_position(), Vec2::new(0, 1));
+    }
+}
@@ -7,20 +7,38 @@ use align::*;
 use direction::Direction;
 use event::*;
 use std::any::Any;
+use std::cell::Cell;
 use std::cmp::max;
-use theme::ColorStyle;
+use theme::{BorderStyle, ColorStyle};
 
 use unicode_width::UnicodeWidthStr;
 use vec::{Vec2, Vec4};
-use view::{Selector, View};
+use view::{FocusContainer, Selector, View, ViewInfo, ViewPath};
 use views::{Button, DummyView, SizedView, TextView};
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 enum Focus {
     Content,
     Button(usize),
 }
 
+impl Focus {
+    // Index in view paths: the content, then the buttons.
+    fn index(self) -> usize {
+        match self {
+            Focus::Content => 0,
+            Focus::Button(i) => i + 1,
+        }
+    }
+
+    fn from_index(i: usize) -> Self {
+        match i {
+            0 => Focus::Content,
+            i => Focus::Button(i - 1),
+        }
+    }
+}
+
 /// Popup-like view with a main content, and optional buttons under it.
 ///
 /// # Examples
@@ -42,6 +60,12 @@ pub struct Dialog {
     focus: Focus,
 
     align: Align,
+
+    // Overrides the theme's border style.
+    border_style: Option<BorderStyle>,
+
+    last_offset: Cell<Vec2>,
+    last_size: Vec2,
 }
 
 new_default!(Dialog);
@@ -64,6 +88,9 @@ impl Dialog {
             padding: Vec4::new(1, 1, 0, 0),
             borders: Vec4::new(1, 1, 1, 1),
             align: Align::top_right(),
+            border_style: None,
+            last_offset: Cell::new(Vec2::zero()),
+            last_size: Vec2::zero(),
         }
     }
 
@@ -139,6 +166,23 @@ impl Dialog {
         self.title = label.into();
     }
 
+    /// Sets the border style, ignoring the theme's.
+    pub fn set_border_style(&mut self, style: BorderStyle) {
+        self.border_style = Some(style);
+    }
+
+    /// Sets the border style, ignoring the theme's.
+    ///
+    /// Chainable variant.
+    pub fn border_style(self, style: BorderStyle) -> Self {
+        self.with(|s| s.set_border_style(style))
+    }
+
+    /// Uses the theme's border style again.
+    pub fn reset_border_style(&mut self) {
+        self.border_style = None;
+    }
+
     /// Sets the padding in the dialog (around content and buttons).
     pub fn padding<T: Into<Vec4>>(mut self, padding: T) -> Self {
         self.padding = padding.into();
@@ -169,15 +213,21 @@ impl Dialog {
         self.padding.right = padding;
         self
     }
-}
 
-impl View for Dialog {
-    fn draw(&self, printer: &Printer) {
+    // Moves the focus, notifying both the old and new focused views.
+    fn move_focus(&mut self, focus: Focus) -> EventResult {
+        self.change_focus(focus.index())
+    }
 
-        // This will be the buttons_height used by the buttons.
-        let mut buttons_height = 0;
-        // Current horizontal position of the next button we'll draw.
+    fn view_mut(&mut self, focus: Focus) -> &mut View {
+        match focus {
+            Focus::Content => &mut *self.content,
+            Focus::Button(i) => &mut self.buttons[i],
+        }
+    }
 
+    // Positions of the buttons, or `None` if the dialog is too small.
+    fn button_offsets(&self, size: Vec2) -> Option<Vec<Vec2>> {
         // Sum of the sizes + len-1 for margins
         let width = self.buttons
             .iter()
@@ -185,38 +235,108 @@ impl View for Dialog {
             .fold(0, |a, b| a + b) +
                     self.buttons.len().saturating_sub(1);
         let overhead = self.padding + self.borders;
-        if printer.size.x < overhead.horizontal() {
-            return;
+        if size.x < overhead.horizontal() {
+            return None;
         }
-        let mut offset = overhead.left +
-                         self.align.h.get_offset(width,
-                                                 printer.size.x -
-                                                 overhead.horizontal());
+        // Current horizontal position of the next button.
+        let mut x = overhead.left +
+                    self.align.h.get_offset(width,
+                                            size.x - overhead.horizontal());
 
         let overhead_bottom = self.padding.bottom + self.borders.bottom + 1;
-
-        let y = match printer.size.y.checked_sub(overhead_bottom) {
+        let y = match size.y.checked_sub(overhead_bottom) {
             Some(y) => y,
-            None => return,
+            None => return None,
         };
 
-        for (i, button) in self.buttons.iter().enumerate() {
-            let size = button.size;
-            // Add some special effect to the focused button
-            button.draw(&printer.sub_printer(Vec2::new(offset, y),
-                                             size,
-                                             self.focus == Focus::Button(i)));
+        let mut offsets = Vec::with_capacity(self.buttons.len());
+        for button in &self.buttons {
+            offsets.push(Vec2::new(x, y));
             // Keep 1 blank between two buttons
-            offset += size.x + 1;
-            // Also keep 1 blank above the buttons
-            buttons_height = max(buttons_height, size.y + 1);
+            x += button.size.x + 1;
         }
+        Some(offsets)
+    }
 
-        // What do we have left?
+    // Size left for the content, after borders, padding and buttons.
+    fn content_size(&self, size: Vec2) -> Option<Vec2> {
+        // Also keep 1 blank above the buttons
+        let buttons_height = self.buttons
+            .iter()
+            .map(|button| button.size.y + 1)
+            .max()
+            .unwrap_or(0);
         let taken = Vec2::new(0, buttons_height) + self.borders.combined() +
                     self.padding.combined();
 
-        let inner_size = match printer.size.checked_sub(taken) {
+        size.checked_sub(taken)
+    }
+}
+
+// Children are identified by their `Focus::index`.
+impl FocusContainer for Dialog {
+    type Child = usize;
+
+    fn last_offset(&self) -> Vec2 {
+        self.last_offset.get()
+    }
+
+    fn child_at(&self, position: Vec2) -> Option<usize> {
+        let inside = |offset: Vec2, size: Vec2| {
+            position.fits(offset) && (position - offset + (1, 1)).fits_in(size)
+        };
+
+        if let Some(inner_size) = self.content_size(self.last_size) {
+            let offset = self.borders.top_left() + self.padding.top_left();
+            if inside(offset, inner_size) {
+                return Some(0);
+            }
+        }
+
+        self.button_offsets(self.last_size).and_then(|offsets| {
+            self.buttons
+                .iter()
+                .zip(offsets)
+                .position(|(button, offset)| inside(offset, button.size))
+                .map(|i| i + 1)
+        })
+    }
+
+    fn focused_child(&self) -> usize {
+        self.focus.index()
+    }
+
+    fn set_focused_child(&mut self, i: usize) {
+        self.focus = Focus::from_index(i);
+    }
+
+    fn child_take_focus(&mut self, i: usize, source: Direction) -> bool {
+        self.view_mut(Focus::from_index(i)).take_focus(source)
+    }
+
+    fn child_on_event(&mut self, i: usize, event: Event) -> EventResult {
+        self.view_mut(Focus::from_index(i)).on_event(event)
+    }
+}
+
+impl View for Dialog {
+    fn draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
+
+        let buttons = match self.button_offsets(printer.size) {
+            Some(buttons) => buttons,
+            None => return,
+        };
+
+        for (i, (button, offset)) in
+            self.buttons.iter().zip(buttons).enumerate() {
+            // Add some special effect to the focused button
+            button.draw(&printer.sub_printer(offset,
+                                             button.size,
+                                             self.focus == Focus::Button(i)));
+        }
+
+        let inner_size = match self.content_size(printer.size) {
             Some(s) => s,
             None => return,
         };
@@ -226,7 +346,8 @@ impl View for Dialog {
                                                inner_size,
                                                self.focus == Focus::Content));
 
-        printer.print_box(Vec2::new(0, 0), printer.size, false);
+        let style = self.border_style.unwrap_or(printer.theme.borders);
+        printer.print_styled_box(Vec2::new(0, 0), printer.size, false, &style);
 
         if !self.title.is_empty() {
             let len = self.title.width();
@@ -234,9 +355,10 @@ impl View for Dialog {
                 return;
             }
             let x = (printer.size.x - len) / 2;
-            printer.with_high_border(false, |printer| {
-                printer.print((x - 2, 0), "┤ ");
-                printer.print((x + len, 0), " ├");
+            let chars = style.chars();
+            printer.with_styled_high_border(&style, false, |printer| {
+                printer.print((x - 2, 0), &format!("{} ", chars.right_tee));
+                printer.print((x + len, 0), &format!(" {}", chars.left_tee));
             });
 
             printer.with_color(ColorStyle::TitlePrimary,
@@ -288,6 +410,8 @@ impl View for Dialog {
     }
 
     fn layout(&mut self, mut size: Vec2) {
+        self.last_size = size;
+
         // Padding and borders are taken, sorry.
         // TODO: handle border-less themes?
         let taken = self.borders.combined() + self.padding.combined();
@@ -309,6 +433,12 @@ impl View for Dialog {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if let Event::Mouse { position, event } = event {
+            if let Some(result) = self.route_mouse_event(position, event) {
+                return result;
+            }
+        }
+
         match self.focus {
             // If we are on the content, we can only go down.
             Focus::Content => {
@@ -319,8 +449,7 @@ impl View for Dialog {
                             Event::Key(Key::Tab) |
                             Event::Shift(Key::Tab) => {
                                 // Default to leftmost button when going down.
-                                self.focus = Focus::Button(0);
-                                EventResult::Consumed(None)
+                                self.move_focus(Focus::Button(0))
                             }
                             _ => EventResult::Ignored,
                         }
@@ -336,16 +465,14 @@ impl View for Dialog {
                             // Up goes back to the content
                             Event::Key(Key::Up) => {
                                 if self.content.take_focus(Direction::down()) {
-                                    self.focus = Focus::Content;
-                                    EventResult::Consumed(None)
+                                    self.move_focus(Focus::Content)
                                 } else {
                                     EventResult::Ignored
                                 }
                             }
                             Event::Shift(Key::Tab) => {
                                 if self.content.take_focus(Direction::back()) {
-                                    self.focus = Focus::Content;
-                                    EventResult::Consumed(None)
+                                    self.move_focus(Focus::Content)
                                 } else {
                                     EventResult::Ignored
                                 }
@@ -353,8 +480,7 @@ impl View for Dialog {
                             Event::Key(Key::Tab) => {
                                 if self.content
                                        .take_focus(Direction::front()) {
-                                    self.focus = Focus::Content;
-                                    EventResult::Consumed(None)
+                                    self.move_focus(Focus::Content)
                                 } else {
                                     EventResult::Ignored
                                 }
@@ -362,12 +488,10 @@ impl View for Dialog {
                             // Left and Right move to other buttons
                             Event::Key(Key::Right) if i + 1 <
                                                       self.buttons.len() => {
-                                self.focus = Focus::Button(i + 1);
-                                EventResult::Consumed(None)
+                                self.move_focus(Focus::Button(i + 1))
                             }
                             Event::Key(Key::Left) if i > 0 => {
-                                self.focus = Focus::Button(i - 1);
-                                EventResult::Consumed(None)
+                                self.move_focus(Focus::Button(i - 1))
                             }
                             _ => EventResult::Ignored,
                         }
@@ -393,11 +517,88 @@ impl View for Dialog {
     }
 
     fn call_on_any<'a>(&mut self, selector: &Selector,
-                       callback: Box<FnMut(&mut Any) + 'a>) {
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        // In paths, the content comes first, then each button.
+        if let Selector::Path(path) = *selector {
+            match path.split_first() {
+                None => callback(self),
+                Some((0, rest)) => {
+                    self.content.call_on_any(&Selector::Path(&rest), callback)
+                }
+                Some((i, ref rest)) if rest.path.is_empty() => {
+                    if let Some(button) = self.buttons.get_mut(i - 1) {
+                        callback(&mut button.view);
+                    }
+                }
+                Some(_) => (),
+            }
+            return;
+        }
+
         self.content.call_on_any(selector, callback);
     }
 
     fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        if let Selector::Path(path) = *selector {
+            return match path.split_first() {
+                       None if self.take_focus(Direction::none()) => Ok(()),
+                       Some((0, rest)) => {
+                           self.content
+                               .focus_view(&Selector::Path(&rest))
+                               .map(|_| self.focus = Focus::Content)
+                       }
+                       Some((i, ref rest)) if rest.path.is_empty() &&
+                                              i <= self.buttons.len() => {
+                           self.focus = Focus::Button(i - 1);
+                           Ok(())
+                       }
+                       _ => Err(()),
+                   };
+        }
+
         self.content.focus_view(selector)
     }
+
+    fn focused_id(&self) -> Option<String> {
+        match self.focus {
+            Focus::Content => self.content.focused_id(),
+            Focus::Button(_) => None,
+        }
+    }
+
+    fn focused_path(&self) -> ViewPath {
+        match self.focus {
+            Focus::Content => {
+                let mut path = self.content.focused_path();
+                path.path.insert(0, 0);
+                path
+            }
+            Focus::Button(i) => ViewPath::from(&[i + 1][..]),
+        }
+    }
+
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        match path.split_first() {
+            Some((0, rest)) => self.content.id_at(&rest),
+            _ => None,
+        }
+    }
+
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        let mut info = ViewInfo::new::<Self>(size);
+        if let Some(inner_size) = self.content_size(size) {
+            info.add_child(self.borders.top_left() + self.padding.top_left(),
+                           self.focus == Focus::Content,
+                           self.content.inspect(inner_size));
+        }
+        if let Some(buttons) = self.button_offsets(size) {
+            for (i, (button, offset)) in
+                self.buttons.iter().zip(buttons).enumerate() {
+                info.add_child(offset,
+                               self.focus == Focus::Button(i),
+                               button.view.inspect(button.size));
+            }
+        }
+        info
+    }
 }
@@ -80,6 +80,9 @@ pub struct EditView {
     /// Callback when <Enter> is pressed.
     on_submit: Option<Rc<Fn(&mut Cursive, &str)>>,
 
+    /// Callback when the focus leaves this view.
+    on_blur: Option<Rc<Fn(&mut Cursive, &str)>>,
+
     /// When `true`, only print `*` instead of the true content.
     secret: bool,
 
@@ -103,6 +106,7 @@ impl EditView {
             last_length: 0, // scrollable: false,
             on_edit: None,
             on_submit: None,
+            on_blur: None,
             secret: false,
             filler: "_".to_string(),
             enabled: true,
@@ -298,6 +302,25 @@ impl EditView {
         self.with(|v| v.set_on_submit(callback))
     }
 
+    /// Sets a callback to be called when the focus leaves this view.
+    ///
+    /// `callback` will be given the content of the view.
+    /// This is a good place to validate the input.
+    pub fn set_on_blur<F>(&mut self, callback: F)
+        where F: Fn(&mut Cursive, &str) + 'static
+    {
+        self.on_blur = Some(Rc::new(callback));
+    }
+
+    /// Sets a callback to be called when the focus leaves this view.
+    ///
+    /// Chainable variant.
+    pub fn on_blur<F>(self, callback: F) -> Self
+        where F: Fn(&mut Cursive, &str) + 'static
+    {
+        self.with(|v| v.set_on_blur(callback))
+    }
+
     /// Enable or disable this view.
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
@@ -547,6 +570,11 @@ impl View for EditView {
                 let content = self.content.clone();
                 return EventResult::with_cb(move |s| { cb(s, &content); });
             }
+            Event::FocusLost if self.on_blur.is_some() => {
+                let cb = self.on_blur.clone().unwrap();
+                let content = self.content.clone();
+                return EventResult::with_cb(move |s| { cb(s, &content); });
+            }
             _ => return EventResult::Ignored,
         }
 
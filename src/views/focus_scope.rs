@@ -0,0 +1,104 @@
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use view::{View, ViewWrapper};
+
+/// Wrapper view that keeps `<Tab>` navigation inside its child.
+///
+/// Usually, when the focus reaches the last element of a layer, `<Tab>`
+/// is ignored. Inside a `FocusScope`, it wraps around to the first one
+/// instead (and `<Shift-Tab>` from the first element goes to the last).
+///
+/// This is mostly useful for modal layers, like dialogs.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate cursive;
+/// # use cursive::Cursive;
+/// # use cursive::views::{Dialog, EditView, FocusScope};
+/// # fn main() {
+/// let mut siv = Cursive::new();
+///
+/// siv.add_layer(FocusScope::new(Dialog::around(EditView::new())
+///                                   .button("Ok", |s| s.pop_layer())
+///                                   .button("Cancel", |s| s.pop_layer())));
+/// # }
+/// ```
+pub struct FocusScope<T: View> {
+    view: T,
+}
+
+impl<T: View> FocusScope<T> {
+    /// Wraps the given view.
+    pub fn new(view: T) -> Self {
+        FocusScope { view: view }
+    }
+
+    /// Returns a reference to the wrapped view.
+    pub fn get_view(&self) -> &T {
+        &self.view
+    }
+
+    /// Returns a mutable reference to the wrapped view.
+    pub fn get_view_mut(&mut self) -> &mut T {
+        &mut self.view
+    }
+
+    // Moves the focus back to one end of the child.
+    fn wrap_around(&mut self, source: Direction) -> EventResult {
+        let lost = self.view.on_event(Event::FocusLost);
+        if !self.view.take_focus(source) {
+            return EventResult::Ignored;
+        }
+        let gained = self.view.on_event(Event::FocusGained);
+
+        EventResult::Consumed(None).and(lost).and(gained)
+    }
+}
+
+impl<T: View> ViewWrapper for FocusScope<T> {
+    wrap_impl!(self.view: T);
+
+    fn wrap_on_event(&mut self, event: Event) -> EventResult {
+        match self.view.on_event(event.clone()) {
+            EventResult::Ignored => match event {
+                Event::Key(Key::Tab) => self.wrap_around(Direction::front()),
+                Event::Shift(Key::Tab) => self.wrap_around(Direction::back()),
+                _ => EventResult::Ignored,
+            },
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vec::Vec2;
+    use view::ViewPath;
+    use views::{EditView, LinearLayout};
+
+    #[test]
+    fn test_wrap_around() {
+        let mut scope = FocusScope::new(LinearLayout::vertical()
+                                            .child(EditView::new())
+                                            .child(EditView::new()));
+        scope.layout(Vec2::new(10, 2));
+        assert!(scope.take_focus(Direction::front()));
+        assert_eq!(scope.focused_path(), ViewPath::from(&[0][..]));
+
+        assert!(scope.on_event(Event::Key(Key::Tab)).is_consumed());
+        assert_eq!(scope.focused_path(), ViewPath::from(&[1][..]));
+
+        // The last view goes back to the first one, and the other way.
+        assert!(scope.on_event(Event::Key(Key::Tab)).is_consumed());
+        assert_eq!(scope.focused_path(), ViewPath::from(&[0][..]));
+
+        assert!(scope.on_event(Event::Shift(Key::Tab)).is_consumed());
+        assert_eq!(scope.focused_path(), ViewPath::from(&[1][..]));
+
+        // Without the scope, the tab would be ignored.
+        let layout = scope.get_view_mut();
+        assert!(!layout.on_event(Event::Key(Key::Tab)).is_consumed());
+    }
+}
@@ -0,0 +1,661 @@
+use Printer;
+use With;
+use direction::{Absolute, Direction};
+use event::{Event, EventResult, Key};
+use std::any::Any;
+use std::cell::Cell;
+use std::cmp::{max, min};
+use vec::Vec2;
+use view::{FocusContainer, Selector, View, ViewInfo, ViewPath};
+
+/// Sizing rule for a column of a [`GridLayout`].
+///
+/// [`GridLayout`]: struct.GridLayout.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// The column always has this width.
+    Fixed(usize),
+    /// The column is as wide as its widest child.
+    Auto,
+    /// The column shares the remaining space with the other weighted
+    /// columns, proportionally to its weight.
+    ///
+    /// It is never narrower than with `Auto`.
+    Weight(usize),
+}
+
+/// Arranges its children in rows and columns.
+///
+/// Each child is placed in a cell, and may span several rows or columns.
+/// All children in a column share the same width, and all children in a
+/// row share the same height.
+///
+/// The arrow keys move the focus to the nearest child in that direction,
+/// and `<Tab>` goes through the children row by row.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{ColumnWidth, EditView, GridLayout, TextView};
+/// let form = GridLayout::new()
+///     .column_width(1, ColumnWidth::Weight(1))
+///     .child(0, 0, TextView::new("Name"))
+///     .child(0, 1, EditView::new())
+///     .child(1, 0, TextView::new("Email address"))
+///     .child(1, 1, EditView::new())
+///     .child_span(2, 0, 1, 2, TextView::new("Both fields are required."));
+/// ```
+pub struct GridLayout {
+    children: Vec<Child>,
+    columns: Vec<ColumnWidth>,
+    focus: usize,
+
+    // Sizes computed by the last layout.
+    widths: Vec<usize>,
+    heights: Vec<usize>,
+
+    last_offset: Cell<Vec2>,
+}
+
+struct Child {
+    view: Box<View>,
+    row: usize,
+    col: usize,
+    row_span: usize,
+    col_span: usize,
+}
+
+new_default!(GridLayout);
+
+// Sum of the given range of sizes.
+fn span(sizes: &[usize], start: usize, len: usize) -> usize {
+    sizes.iter().skip(start).take(len).sum()
+}
+
+// Distance between the ranges `[a, a + a_len)` and `[b, b + b_len)`.
+// Returns 0 if they overlap.
+fn gap(a: usize, a_len: usize, b: usize, b_len: usize) -> usize {
+    if a + a_len <= b {
+        b - (a + a_len) + 1
+    } else if b + b_len <= a {
+        a - (b + b_len) + 1
+    } else {
+        0
+    }
+}
+
+// Reduces `sizes` so they fit in `max`, cutting the last ones first.
+fn cap(sizes: &mut [usize], max: usize) {
+    let mut available = max;
+    for size in sizes.iter_mut() {
+        *size = min(*size, available);
+        available -= *size;
+    }
+}
+
+impl GridLayout {
+    /// Creates a new, empty grid.
+    pub fn new() -> Self {
+        GridLayout {
+            children: Vec::new(),
+            columns: Vec::new(),
+            focus: 0,
+            widths: Vec::new(),
+            heights: Vec::new(),
+            last_offset: Cell::new(Vec2::zero()),
+        }
+    }
+
+    /// Adds a child in the given cell.
+    pub fn add_child<V: View + 'static>(&mut self, row: usize, col: usize,
+                                        view: V) {
+        self.add_child_span(row, col, 1, 1, view);
+    }
+
+    /// Adds a child in the given cell.
+    ///
+    /// Chainable variant.
+    pub fn child<V: View + 'static>(self, row: usize, col: usize, view: V)
+                                    -> Self {
+        self.with(|s| s.add_child(row, col, view))
+    }
+
+    /// Adds a child spanning several rows and columns.
+    ///
+    /// Spans of 0 are treated as 1.
+    pub fn add_child_span<V: View + 'static>(&mut self, row: usize,
+                                             col: usize, row_span: usize,
+                                             col_span: usize, view: V) {
+        self.children.push(Child {
+            view: Box::new(view),
+            row: row,
+            col: col,
+            row_span: max(row_span, 1),
+            col_span: max(col_span, 1),
+        });
+    }
+
+    /// Adds a child spanning several rows and columns.
+    ///
+    /// Chainable variant.
+    pub fn child_span<V: View + 'static>(self, row: usize, col: usize,
+                                         row_span: usize, col_span: usize,
+                                         view: V)
+                                         -> Self {
+        self.with(|s| s.add_child_span(row, col, row_span, col_span, view))
+    }
+
+    /// Sets the sizing rule for the given column.
+    ///
+    /// Columns are `ColumnWidth::Auto` by default.
+    pub fn set_column_width(&mut self, col: usize, width: ColumnWidth) {
+        if self.columns.len() <= col {
+            self.columns.resize(col + 1, ColumnWidth::Auto);
+        }
+        self.columns[col] = width;
+    }
+
+    /// Sets the sizing rule for the given column.
+    ///
+    /// Chainable variant.
+    pub fn column_width(self, col: usize, width: ColumnWidth) -> Self {
+        self.with(|s| s.set_column_width(col, width))
+    }
+
+    /// Returns the number of columns in this grid.
+    pub fn columns(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| child.col + child.col_span)
+            .max()
+            .map_or(self.columns.len(), |n| max(n, self.columns.len()))
+    }
+
+    /// Returns the number of rows in this grid.
+    pub fn rows(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| child.row + child.row_span)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// Returns `true` if this grid has no children.
+    pub fn is_empty(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns the view in the given cell, if any.
+    pub fn get_child(&self, row: usize, col: usize) -> Option<&View> {
+        self.find_child(row, col).map(|i| &*self.children[i].view)
+    }
+
+    /// Returns the view in the given cell, if any.
+    pub fn get_child_mut(&mut self, row: usize, col: usize)
+                         -> Option<&mut View> {
+        match self.find_child(row, col) {
+            Some(i) => Some(&mut *self.children[i].view),
+            None => None,
+        }
+    }
+
+    /// Removes the view in the given cell, and returns it.
+    pub fn remove_child(&mut self, row: usize, col: usize)
+                        -> Option<Box<View>> {
+        self.find_child(row, col).map(|i| {
+            if self.focus > i || self.focus + 1 == self.children.len() {
+                self.focus = self.focus.saturating_sub(1);
+            }
+            self.children.remove(i).view
+        })
+    }
+
+    // Finds the child covering the given cell.
+    fn find_child(&self, row: usize, col: usize) -> Option<usize> {
+        self.children.iter().position(|child| {
+            gap(child.row, child.row_span, row, 1) == 0 &&
+            gap(child.col, child.col_span, col, 1) == 0
+        })
+    }
+
+    fn column_width_rule(&self, col: usize) -> ColumnWidth {
+        self.columns.get(col).cloned().unwrap_or(ColumnWidth::Auto)
+    }
+
+    // Computes the minimal width of each column.
+    fn min_widths(&mut self, req: Vec2) -> Vec<usize> {
+        let mut widths: Vec<usize> = (0..self.columns())
+            .map(|col| match self.column_width_rule(col) {
+                     ColumnWidth::Fixed(width) => width,
+                     _ => 0,
+                 })
+            .collect();
+
+        // Start with single-column children, then make room for the others.
+        let mut children: Vec<&mut Child> =
+            self.children.iter_mut().collect();
+        children.sort_by_key(|child| child.col_span);
+        for child in children {
+            let fixed = span(&widths, child.col, child.col_span);
+            let needed = child.view.required_size(req).x;
+            if needed <= fixed {
+                continue;
+            }
+
+            // Grow the last non-fixed column, if any.
+            let columns = &self.columns;
+            let target = (child.col..child.col + child.col_span)
+                .rev()
+                .find(|&col| match columns.get(col) {
+                          Some(&ColumnWidth::Fixed(_)) => false,
+                          _ => true,
+                      });
+            if let Some(col) = target {
+                widths[col] += needed - fixed;
+            }
+        }
+
+        widths
+    }
+
+    // Gives the extra space to the weighted columns.
+    fn distribute(&self, widths: &mut [usize], available: usize) {
+        let total: usize = widths.iter().sum();
+        let mut extra = match available.checked_sub(total) {
+            Some(extra) if extra > 0 => extra,
+            _ => return,
+        };
+
+        let weights: Vec<usize> = (0..widths.len())
+            .map(|col| match self.column_width_rule(col) {
+                     ColumnWidth::Weight(weight) => weight,
+                     _ => 0,
+                 })
+            .collect();
+        let total_weight: usize = weights.iter().sum();
+        if total_weight == 0 {
+            return;
+        }
+
+        let offered = extra;
+        let mut last = 0;
+        for (col, &weight) in weights.iter().enumerate() {
+            if weight > 0 {
+                let share = offered * weight / total_weight;
+                widths[col] += share;
+                extra -= share;
+                last = col;
+            }
+        }
+
+        // Rounding leftovers go to the last weighted column.
+        widths[last] += extra;
+    }
+
+    // Computes the height of each row, given the column widths.
+    fn min_heights(&mut self, widths: &[usize], req: Vec2) -> Vec<usize> {
+        let mut heights = vec![0; self.rows()];
+
+        let mut children: Vec<&mut Child> =
+            self.children.iter_mut().collect();
+        children.sort_by_key(|child| child.row_span);
+        for child in children {
+            let width = span(widths, child.col, child.col_span);
+            let height = span(&heights, child.row, child.row_span);
+            let needed = child.view
+                .required_size(Vec2::new(width, req.y))
+                .y;
+            if needed > height {
+                heights[child.row + child.row_span - 1] += needed - height;
+            }
+        }
+
+        heights
+    }
+
+    fn cell_offset(&self, child: &Child) -> Vec2 {
+        Vec2::new(span(&self.widths, 0, child.col),
+                  span(&self.heights, 0, child.row))
+    }
+
+    fn cell_size(&self, child: &Child) -> Vec2 {
+        Vec2::new(span(&self.widths, child.col, child.col_span),
+                  span(&self.heights, child.row, child.row_span))
+    }
+
+    // Children indexes, row by row.
+    fn tab_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.children.len()).collect();
+        order.sort_by_key(|&i| (self.children[i].row, self.children[i].col));
+        order
+    }
+
+    fn move_focus_tab(&mut self, source: Direction) -> EventResult {
+        let mut order = self.tab_order();
+        if source == Direction::back() {
+            order.reverse();
+        }
+
+        let start = order.iter()
+            .position(|&i| i == self.focus)
+            .map_or(0, |p| p + 1);
+        for &i in &order[start..] {
+            if self.children[i].view.take_focus(source) {
+                return self.change_focus(i);
+            }
+        }
+
+        EventResult::Ignored
+    }
+
+    // Moves the focus to the nearest child in the given direction.
+    fn move_focus(&mut self, target: Absolute) -> EventResult {
+        if self.children.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        let mut candidates: Vec<(usize, usize, usize)> = {
+            let current = &self.children[self.focus];
+            self.children
+                .iter()
+                .enumerate()
+                .filter_map(|(i, child)| {
+                    let (main, cross) = match target {
+                        Absolute::Left if child.col + child.col_span <=
+                                          current.col => {
+                            (current.col - child.col,
+                             gap(child.row, child.row_span,
+                                 current.row, current.row_span))
+                        }
+                        Absolute::Right if current.col + current.col_span <=
+                                           child.col => {
+                            (child.col - current.col,
+                             gap(child.row, child.row_span,
+                                 current.row, current.row_span))
+                        }
+                        Absolute::Up if child.row + child.row_span <=
+                                        current.row => {
+                            (current.row - child.row,
+                             gap(child.col, child.col_span,
+                                 current.col, current.col_span))
+                        }
+                        Absolute::Down if current.row + current.row_span <=
+                                          child.row => {
+                            (child.row - current.row,
+                             gap(child.col, child.col_span,
+                                 current.col, current.col_span))
+                        }
+                        _ => return None,
+                    };
+                    Some((cross, main, i))
+                })
+                .collect()
+        };
+
+        // Prefer children aligned with the current one, then the closest.
+        candidates.sort();
+
+        // The focus comes from the opposite side.
+        let source = match target {
+            Absolute::Left => Direction::right(),
+            Absolute::Right => Direction::left(),
+            Absolute::Up => Direction::down(),
+            Absolute::Down => Direction::up(),
+            Absolute::None => Direction::none(),
+        };
+
+        for (_, _, i) in candidates {
+            if self.children[i].view.take_focus(source) {
+                return self.change_focus(i);
+            }
+        }
+
+        EventResult::Ignored
+    }
+}
+
+impl FocusContainer for GridLayout {
+    type Child = usize;
+
+    fn last_offset(&self) -> Vec2 {
+        self.last_offset.get()
+    }
+
+    fn child_at(&self, position: Vec2) -> Option<usize> {
+        self.children.iter().position(|child| {
+            let offset = self.cell_offset(child);
+            position.fits(offset) &&
+            (position - offset + (1, 1)).fits_in(self.cell_size(child))
+        })
+    }
+
+    fn focused_child(&self) -> usize {
+        self.focus
+    }
+
+    fn set_focused_child(&mut self, i: usize) {
+        self.focus = i;
+    }
+
+    fn child_take_focus(&mut self, i: usize, source: Direction) -> bool {
+        self.children[i].view.take_focus(source)
+    }
+
+    fn child_on_event(&mut self, i: usize, event: Event) -> EventResult {
+        self.children[i].view.on_event(event)
+    }
+}
+
+impl View for GridLayout {
+    fn draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
+
+        for (i, child) in self.children.iter().enumerate() {
+            let printer = printer.sub_printer(self.cell_offset(child),
+                                              self.cell_size(child),
+                                              i == self.focus);
+            child.view.draw(&printer);
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let widths = self.min_widths(req);
+        let heights = self.min_heights(&widths, req);
+
+        Vec2::new(widths.iter().sum(), heights.iter().sum())
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        let mut widths = self.min_widths(size);
+        self.distribute(&mut widths, size.x);
+        cap(&mut widths, size.x);
+
+        let mut heights = self.min_heights(&widths, size);
+        cap(&mut heights, size.y);
+
+        self.widths = widths;
+        self.heights = heights;
+
+        for i in 0..self.children.len() {
+            let size = self.cell_size(&self.children[i]);
+            self.children[i].view.layout(size);
+        }
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        let mut order = self.tab_order();
+        match source {
+            Direction::Abs(Absolute::Right) |
+            Direction::Abs(Absolute::Down) => order.reverse(),
+            _ if source == Direction::back() => order.reverse(),
+            _ => (),
+        }
+
+        for i in order {
+            if self.children[i].view.take_focus(source) {
+                self.focus = i;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if self.children.is_empty() {
+            return EventResult::Ignored;
+        }
+
+        if let Event::Mouse { position, event } = event {
+            if let Some(result) = self.route_mouse_event(position, event) {
+                return result;
+            }
+        }
+
+        match self.children[self.focus].view.on_event(event.clone()) {
+            EventResult::Ignored => match event {
+                Event::Key(Key::Tab) => {
+                    self.move_focus_tab(Direction::front())
+                }
+                Event::Shift(Key::Tab) => {
+                    self.move_focus_tab(Direction::back())
+                }
+                Event::Key(Key::Left) => self.move_focus(Absolute::Left),
+                Event::Key(Key::Right) => self.move_focus(Absolute::Right),
+                Event::Key(Key::Up) => self.move_focus(Absolute::Up),
+                Event::Key(Key::Down) => self.move_focus(Absolute::Down),
+                _ => EventResult::Ignored,
+            },
+            res => res,
+        }
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        if let Selector::Path(path) = *selector {
+            match path.split_first() {
+                None => callback(self),
+                Some((i, rest)) => {
+                    if let Some(child) = self.children.get_mut(i) {
+                        let selector = Selector::Path(&rest);
+                        child.view.call_on_any(&selector, callback);
+                    }
+                }
+            }
+            return;
+        }
+
+        for child in &mut self.children {
+            child.view.call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        if let Selector::Path(path) = *selector {
+            let (i, rest) = match path.split_first() {
+                Some(turn) => turn,
+                None if self.take_focus(Direction::none()) => return Ok(()),
+                None => return Err(()),
+            };
+            let found = match self.children.get_mut(i) {
+                Some(child) => {
+                    child.view.focus_view(&Selector::Path(&rest)).is_ok()
+                }
+                None => false,
+            };
+            if !found {
+                return Err(());
+            }
+            self.focus = i;
+            return Ok(());
+        }
+
+        for (i, child) in self.children.iter_mut().enumerate() {
+            if child.view.focus_view(selector).is_ok() {
+                self.focus = i;
+                return Ok(());
+            }
+        }
+
+        Err(())
+    }
+
+    fn focused_id(&self) -> Option<String> {
+        self.children
+            .get(self.focus)
+            .and_then(|child| child.view.focused_id())
+    }
+
+    fn focused_path(&self) -> ViewPath {
+        let mut path = ViewPath::new();
+        if let Some(child) = self.children.get(self.focus) {
+            path = child.view.focused_path();
+            path.path.insert(0, self.focus);
+        }
+        path
+    }
+
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        path.split_first().and_then(|(i, rest)| {
+            self.children.get(i).and_then(|child| child.view.id_at(&rest))
+        })
+    }
+
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        let mut info = ViewInfo::new::<Self>(size);
+        for (i, child) in self.children.iter().enumerate() {
+            let view = child.view.inspect(self.cell_size(child));
+            info.add_child(self.cell_offset(child), i == self.focus, view);
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use views::{Button, DummyView, TextView};
+
+    #[test]
+    fn test_layout() {
+        let mut grid = GridLayout::new()
+            .column_width(0, ColumnWidth::Fixed(3))
+            .column_width(2, ColumnWidth::Weight(1))
+            .child(0, 0, DummyView)
+            .child(0, 1, TextView::new("abcd"))
+            .child(0, 2, TextView::new("ab"))
+            .child_span(1, 0, 2, 3, TextView::new("abcdefghijkl"));
+
+        assert_eq!(grid.required_size(Vec2::new(80, 24)), Vec2::new(12, 2));
+
+        grid.layout(Vec2::new(20, 24));
+        assert_eq!(grid.widths, vec![3, 4, 13]);
+        assert_eq!(grid.heights, vec![1, 0, 1]);
+    }
+
+    #[test]
+    fn test_focus() {
+        let mut grid = GridLayout::new()
+            .child(0, 0, TextView::new("label"))
+            .child(0, 1, Button::new("A", |_| ()))
+            .child(1, 0, Button::new("B", |_| ()))
+            .child(1, 1, Button::new("C", |_| ()));
+
+        assert!(grid.take_focus(Direction::front()));
+        assert_eq!(grid.focus, 1);
+
+        grid.on_event(Event::Key(Key::Down));
+        assert_eq!(grid.focus, 3);
+        grid.on_event(Event::Key(Key::Left));
+        assert_eq!(grid.focus, 2);
+        // The label can't take the focus: go to the nearest button.
+        grid.on_event(Event::Key(Key::Up));
+        assert_eq!(grid.focus, 1);
+        assert!(!grid.on_event(Event::Key(Key::Up)).is_consumed());
+    }
+}
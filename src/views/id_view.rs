@@ -1,9 +1,11 @@
+use direction::Direction;
 use owning_ref::{RcRef, OwningHandle};
 use std::any::Any;
 
 use std::cell::{RefCell, RefMut};
 use std::rc::Rc;
-use view::{Selector, View, ViewWrapper};
+use vec::Vec2;
+use view::{Selector, View, ViewInfo, ViewPath, ViewWrapper};
 
 /// Wrapper around a view to provide interior mutability.
 pub struct IdView<V: View> {
@@ -64,6 +66,7 @@ impl<T: View + 'static> ViewWrapper for IdView<T> {
     ) {
         match selector {
             &Selector::Id(id) if id == self.id => callback(self),
+            &Selector::Path(path) if path.path.is_empty() => callback(self),
             s => {
                 self.view.try_borrow_mut().ok().map(|mut v| {
                     v.call_on_any(s, callback)
@@ -72,9 +75,45 @@ impl<T: View + 'static> ViewWrapper for IdView<T> {
         }
     }
 
+    fn wrap_focused_id(&self) -> Option<String> {
+        self.view
+            .try_borrow()
+            .ok()
+            .and_then(|v| v.focused_id())
+            .or_else(|| Some(self.id.clone()))
+    }
+
+    fn wrap_id_at(&self, path: &ViewPath) -> Option<String> {
+        let id = self.view.try_borrow().ok().and_then(|v| v.id_at(path));
+        if id.is_none() && path.path.is_empty() {
+            Some(self.id.clone())
+        } else {
+            id
+        }
+    }
+
+    fn wrap_inspect(&self, size: Vec2) -> ViewInfo {
+        // Describe the inner view directly, it is the one users look for.
+        let mut info = match self.view.try_borrow() {
+            Ok(v) => v.inspect(size),
+            Err(_) => ViewInfo::new::<Self>(size),
+        };
+        if info.id.is_none() {
+            info.id = Some(self.id.clone());
+        }
+        info
+    }
+
     fn wrap_focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
         match selector {
-            &Selector::Id(id) if id == self.id => Ok(()),
+            &Selector::Id(id) if id == self.id => {
+                // The view may refuse the focus, for instance if disabled.
+                if self.wrap_take_focus(Direction::none()) {
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            }
             s => {
                 self.view.try_borrow_mut().map_err(|_| ()).and_then(
                     |mut v| {
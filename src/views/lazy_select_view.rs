@@ -0,0 +1,431 @@
+use Cursive;
+use Printer;
+use With;
+use align::HAlign;
+use direction::Direction;
+use event::{Callback, Event, EventResult, Key};
+use std::cmp::min;
+use std::rc::Rc;
+use theme::ColorStyle;
+
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::{ScrollBase, View};
+
+/// Provides items to a [`LazySelectView`] on demand.
+///
+/// Only the rows currently visible are ever requested, so a source can
+/// front a very large data set (a database query, a log index, ...)
+/// without loading it in memory.
+///
+/// [`LazySelectView`]: struct.LazySelectView.html
+pub trait SelectSource {
+    /// Type of the values given to the callbacks.
+    type Item: 'static;
+
+    /// Returns the number of items available.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the source has no item.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the label to display for the item at the given position.
+    fn label(&self, i: usize) -> String;
+
+    /// Returns the value for the item at the given position.
+    ///
+    /// This is only called when a callback needs it.
+    fn value(&self, i: usize) -> Self::Item;
+
+    /// Returns the width the view should request, if known.
+    ///
+    /// Labels are never all measured, so by default the view simply takes
+    /// all the horizontal space it is offered.
+    fn width(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<T: Clone + 'static> SelectSource for Vec<(String, T)> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn label(&self, i: usize) -> String {
+        self[i].0.clone()
+    }
+
+    fn value(&self, i: usize) -> T {
+        self[i].1.clone()
+    }
+}
+
+/// View to select an item among a list provided by a [`SelectSource`].
+///
+/// Unlike [`SelectView`], items are not stored in the view: labels are
+/// fetched from the source when their row is drawn, and values when a
+/// callback needs them. Memory and drawing time do not depend on the number
+/// of items.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate cursive;
+/// # use cursive::Cursive;
+/// # use cursive::views::{Dialog, LazySelectView, SelectSource};
+/// # use cursive::traits::*;
+/// # fn main() {
+/// struct Numbers;
+///
+/// impl SelectSource for Numbers {
+///     type Item = usize;
+///
+///     fn len(&self) -> usize {
+///         1_000_000
+///     }
+///
+///     fn label(&self, i: usize) -> String {
+///         format!("Row {}", i)
+///     }
+///
+///     fn value(&self, i: usize) -> usize {
+///         i
+///     }
+/// }
+///
+/// let select = LazySelectView::new(Numbers).on_submit(|s, &i| {
+///     s.add_layer(Dialog::info(format!("You picked row {}", i)));
+/// });
+///
+/// let mut siv = Cursive::new();
+/// siv.add_layer(Dialog::around(select.fixed_size((20, 10))));
+/// # }
+/// ```
+///
+/// [`SelectSource`]: trait.SelectSource.html
+/// [`SelectView`]: struct.SelectView.html
+pub struct LazySelectView<S: SelectSource> {
+    source: S,
+    enabled: bool,
+    focus: usize,
+    scrollbase: ScrollBase,
+    // It will be called whenever "Enter" is pressed.
+    on_submit: Option<Rc<Fn(&mut Cursive, &S::Item)>>,
+    // This callback is called when the selection is changed.
+    on_select: Option<Rc<Fn(&mut Cursive, &S::Item)>>,
+    align: HAlign,
+}
+
+impl<S: SelectSource> LazySelectView<S> {
+    impl_enabled!(self.enabled);
+
+    /// Creates a new view reading its items from `source`.
+    pub fn new(source: S) -> Self {
+        LazySelectView {
+            source: source,
+            enabled: true,
+            focus: 0,
+            scrollbase: ScrollBase::new(),
+            on_submit: None,
+            on_select: None,
+            align: HAlign::Left,
+        }
+    }
+
+    /// Returns a reference to the source.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns a mutable reference to the source.
+    ///
+    /// The number of items is read again on the next layout, so the source
+    /// is free to grow or shrink.
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+
+    /// Replaces the source, and moves the selection back to the top.
+    pub fn set_source(&mut self, source: S) {
+        self.source = source;
+        self.focus = 0;
+        self.scrollbase.scroll_top();
+    }
+
+    /// Sets a callback to be used when an item is selected.
+    pub fn set_on_select<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, &S::Item) + 'static,
+    {
+        self.on_select = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when an item is selected.
+    ///
+    /// Chainable variant.
+    pub fn on_select<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, &S::Item) + 'static,
+    {
+        self.with(|s| s.set_on_select(cb))
+    }
+
+    /// Sets a callback to be used when `<Enter>` is pressed.
+    ///
+    /// The value of the item currently selected will be given to the
+    /// callback.
+    pub fn set_on_submit<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, &S::Item) + 'static,
+    {
+        self.on_submit = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when `<Enter>` is pressed.
+    ///
+    /// Chainable variant.
+    pub fn on_submit<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, &S::Item) + 'static,
+    {
+        self.with(|s| s.set_on_submit(cb))
+    }
+
+    /// Sets the horizontal alignment for this view.
+    pub fn h_align(mut self, h: HAlign) -> Self {
+        self.align = h;
+
+        self
+    }
+
+    /// Returns the value of the currently selected item.
+    ///
+    /// Returns `None` if the source is empty.
+    pub fn selection(&self) -> Option<S::Item> {
+        self.selected_id().map(|i| self.source.value(i))
+    }
+
+    /// Returns the id of the item currently selected.
+    ///
+    /// Returns `None` if the source is empty.
+    pub fn selected_id(&self) -> Option<usize> {
+        if self.source.is_empty() {
+            None
+        } else {
+            Some(min(self.focus, self.source.len() - 1))
+        }
+    }
+
+    /// Moves the selection to the given position.
+    pub fn set_selection(&mut self, i: usize) {
+        self.focus = min(i, self.source.len().saturating_sub(1));
+        self.scrollbase.scroll_to(self.focus);
+    }
+
+    /// Moves the selection up by the given number of rows.
+    pub fn select_up(&mut self, n: usize) {
+        let focus = self.focus.saturating_sub(n);
+        self.set_selection(focus);
+    }
+
+    /// Moves the selection down by the given number of rows.
+    pub fn select_down(&mut self, n: usize) {
+        let focus = self.focus + n;
+        self.set_selection(focus);
+    }
+
+    fn draw_item(&self, printer: &Printer, i: usize) {
+        let label = self.source.label(i);
+        let l = label.width();
+        let x = self.align.get_offset(l, printer.size.x);
+        printer.print_hline((0, 0), printer.size.x, " ");
+        printer.print((x, 0), &label);
+    }
+}
+
+impl<S: SelectSource + 'static> View for LazySelectView<S> {
+    fn draw(&self, printer: &Printer) {
+        self.scrollbase.draw(printer, |printer, i| {
+            printer.with_selection(i == self.focus, |printer| {
+                if i != self.focus && !self.enabled {
+                    printer.with_color(ColorStyle::Secondary, |printer| {
+                        self.draw_item(printer, i)
+                    });
+                } else {
+                    self.draw_item(printer, i);
+                }
+            });
+        });
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let h = self.source.len();
+        let w = match self.source.width() {
+            // Add 2 spaces for the scrollbar if we need
+            Some(w) if req.y < h => w + 2,
+            Some(w) => w,
+            None => req.x,
+        };
+
+        Vec2::new(w, h)
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::Key(Key::Up) if self.focus > 0 => self.select_up(1),
+            Event::Key(Key::Down) if self.focus + 1 < self.source.len() => {
+                self.select_down(1)
+            }
+            Event::Key(Key::PageUp) => self.select_up(10),
+            Event::Key(Key::PageDown) => self.select_down(10),
+            Event::Key(Key::Home) => self.set_selection(0),
+            Event::Key(Key::End) => {
+                let last = self.source.len().saturating_sub(1);
+                self.set_selection(last);
+            }
+            Event::Key(Key::Enter) if self.on_submit.is_some() => {
+                let cb = self.on_submit.clone().unwrap();
+                return EventResult::Consumed(self.selection().map(|v| {
+                    Callback::from_fn(move |s| cb(s, &v))
+                }));
+            }
+            _ => return EventResult::Ignored,
+        }
+
+        let cb = self.on_select.clone();
+        EventResult::Consumed(cb.and_then(|cb| {
+            self.selection()
+                .map(|v| Callback::from_fn(move |s| cb(s, &v)))
+        }))
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        self.enabled && !self.source.is_empty()
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        let len = self.source.len();
+        self.focus = min(self.focus, len.saturating_sub(1));
+        self.scrollbase.set_heights(size.y, len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    // Source keeping track of what the view asks for.
+    struct Probe {
+        len: usize,
+        width: Option<usize>,
+        labels: Cell<usize>,
+        values: RefCell<Vec<usize>>,
+    }
+
+    impl Probe {
+        fn new(len: usize) -> Self {
+            Probe {
+                len: len,
+                width: None,
+                labels: Cell::new(0),
+                values: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl SelectSource for Probe {
+        type Item = usize;
+
+        fn len(&self) -> usize {
+            self.len
+        }
+
+        fn label(&self, i: usize) -> String {
+            self.labels.set(self.labels.get() + 1);
+            i.to_string()
+        }
+
+        fn value(&self, i: usize) -> usize {
+            self.values.borrow_mut().push(i);
+            i
+        }
+
+        fn width(&self) -> Option<usize> {
+            self.width
+        }
+    }
+
+    #[test]
+    fn test_required_size() {
+        let mut view = LazySelectView::new(Probe::new(1_000_000));
+        assert_eq!(view.required_size(Vec2::new(30, 10)),
+                   Vec2::new(30, 1_000_000));
+
+        // Room is kept for the scrollbar.
+        view.source_mut().width = Some(12);
+        assert_eq!(view.required_size(Vec2::new(30, 10)),
+                   Vec2::new(14, 1_000_000));
+        assert_eq!(view.source().labels.get(), 0);
+    }
+
+    #[test]
+    fn test_shrinking_source() {
+        let mut view = LazySelectView::new(Probe::new(10));
+        view.layout(Vec2::new(10, 5));
+        view.set_selection(9);
+        assert_eq!(view.selected_id(), Some(9));
+
+        // The selection stays in range, even before the next layout.
+        view.source_mut().len = 3;
+        assert_eq!(view.selected_id(), Some(2));
+        view.layout(Vec2::new(10, 5));
+        assert!(view.on_event(Event::Key(Key::Up)).is_consumed());
+        assert_eq!(view.selected_id(), Some(1));
+
+        assert!(view.on_event(Event::Key(Key::PageDown)).is_consumed());
+        assert_eq!(view.selected_id(), Some(2));
+        assert!(!view.on_event(Event::Key(Key::Down)).is_consumed());
+        assert_eq!(view.selected_id(), Some(2));
+    }
+
+    #[test]
+    fn test_empty_source() {
+        let mut view = LazySelectView::new(Probe::new(0)).on_submit(|_, _| ());
+        view.layout(Vec2::new(10, 5));
+        assert!(!view.take_focus(Direction::none()));
+        assert_eq!(view.selected_id(), None);
+        assert_eq!(view.selection(), None);
+        assert!(!view.on_event(Event::Key(Key::Down)).is_consumed());
+        match view.on_event(Event::Key(Key::Enter)) {
+            EventResult::Consumed(None) => (),
+            _ => panic!("no item to submit"),
+        }
+    }
+
+    #[test]
+    fn test_callback_values() {
+        let mut view = LazySelectView::new(Probe::new(100));
+        view.layout(Vec2::new(10, 5));
+
+        // Values are only fetched when a callback needs them.
+        view.on_event(Event::Key(Key::Down));
+        assert!(view.source().values.borrow().is_empty());
+
+        view.set_on_select(|_, _| ());
+        view.set_on_submit(|_, _| ());
+        match view.on_event(Event::Key(Key::End)) {
+            EventResult::Consumed(Some(_)) => (),
+            _ => panic!("on_select was not called"),
+        }
+        match view.on_event(Event::Key(Key::Enter)) {
+            EventResult::Consumed(Some(_)) => (),
+            _ => panic!("on_submit was not called"),
+        }
+        assert_eq!(*view.source().values.borrow(), vec![99, 99]);
+    }
+}
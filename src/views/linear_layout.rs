@@ -5,9 +5,10 @@ use direction;
 use event::{Event, EventResult, Key};
 
 use std::any::Any;
+use std::cell::Cell;
 use std::cmp::min;
 use vec::Vec2;
-use view::{Selector, SizeCache};
+use view::{FocusContainer, Selector, SizeCache, ViewInfo, ViewPath};
 use view::View;
 
 /// Arranges its children linearly according to its orientation.
@@ -17,6 +18,8 @@ pub struct LinearLayout {
     focus: usize,
 
     cache: Option<XY<SizeCache>>,
+
+    last_offset: Cell<Vec2>,
 }
 
 struct Child {
@@ -56,6 +59,7 @@ impl LinearLayout {
             orientation: orientation,
             focus: 0,
             cache: None,
+            last_offset: Cell::new(Vec2::zero()),
         }
     }
 
@@ -162,8 +166,7 @@ impl LinearLayout {
         } else {
             return EventResult::Ignored;
         };
-        self.focus = i;
-        EventResult::Consumed(None)
+        self.change_focus(i)
     }
 }
 
@@ -176,8 +179,48 @@ fn try_focus((i, child): (usize, &mut Child), source: direction::Direction)
     }
 }
 
+impl FocusContainer for LinearLayout {
+    type Child = usize;
+
+    fn last_offset(&self) -> Vec2 {
+        self.last_offset.get()
+    }
+
+    fn child_at(&self, position: Vec2) -> Option<usize> {
+        let mut offset = Vec2::zero();
+        for (i, child) in self.children.iter().enumerate() {
+            if position.fits(offset) &&
+               (position - offset + (1, 1)).fits_in(child.size) {
+                return Some(i);
+            }
+            *self.orientation.get_ref(&mut offset) += self.orientation
+                .get(&child.size);
+        }
+        None
+    }
+
+    fn focused_child(&self) -> usize {
+        self.focus
+    }
+
+    fn set_focused_child(&mut self, i: usize) {
+        self.focus = i;
+    }
+
+    fn child_take_focus(&mut self, i: usize, source: direction::Direction)
+                        -> bool {
+        self.children[i].view.take_focus(source)
+    }
+
+    fn child_on_event(&mut self, i: usize, event: Event) -> EventResult {
+        self.children[i].view.on_event(event)
+    }
+}
+
 impl View for LinearLayout {
     fn draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
+
         // Use pre-computed sizes
         let mut offset = Vec2::zero();
         for (i, child) in self.children.iter().enumerate() {
@@ -353,6 +396,12 @@ impl View for LinearLayout {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
+        if let Event::Mouse { position, event } = event {
+            if let Some(result) = self.route_mouse_event(position, event) {
+                return result;
+            }
+        }
+
         match self.children[self.focus].view.on_event(event.clone()) {
             EventResult::Ignored => {
                 match event {
@@ -397,12 +446,46 @@ impl View for LinearLayout {
 
     fn call_on_any<'a>(&mut self, selector: &Selector,
                     mut callback: Box<FnMut(&mut Any) + 'a>) {
+        if let Selector::Path(path) = *selector {
+            match path.split_first() {
+                None => callback(self),
+                Some((i, rest)) => {
+                    if let Some(child) = self.children.get_mut(i) {
+                        let selector = Selector::Path(&rest);
+                        child.view.call_on_any(&selector, callback);
+                    }
+                }
+            }
+            return;
+        }
+
         for child in &mut self.children {
             child.view.call_on_any(selector, Box::new(|any| callback(any)));
         }
     }
 
     fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        if let Selector::Path(path) = *selector {
+            let (i, rest) = match path.split_first() {
+                Some(turn) => turn,
+                None if self.take_focus(direction::Direction::none()) => {
+                    return Ok(())
+                }
+                None => return Err(()),
+            };
+            let found = match self.children.get_mut(i) {
+                Some(child) => {
+                    child.view.focus_view(&Selector::Path(&rest)).is_ok()
+                }
+                None => false,
+            };
+            if !found {
+                return Err(());
+            }
+            self.focus = i;
+            return Ok(());
+        }
+
         for (i, child) in self.children.iter_mut().enumerate() {
             if child.view.focus_view(selector).is_ok() {
                 self.focus = i;
@@ -412,4 +495,73 @@ impl View for LinearLayout {
 
         Err(())
     }
+
+    fn focused_id(&self) -> Option<String> {
+        self.children
+            .get(self.focus)
+            .and_then(|child| child.view.focused_id())
+    }
+
+    fn focused_path(&self) -> ViewPath {
+        let mut path = ViewPath::new();
+        if let Some(child) = self.children.get(self.focus) {
+            path = child.view.focused_path();
+            path.path.insert(0, self.focus);
+        }
+        path
+    }
+
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        path.split_first().and_then(|(i, rest)| {
+            self.children.get(i).and_then(|child| child.view.id_at(&rest))
+        })
+    }
+
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        let mut info = ViewInfo::new::<Self>(size);
+        let mut offset = Vec2::zero();
+        for (i, child) in self.children.iter().enumerate() {
+            let view = child.view.inspect(child.size);
+            info.add_child(offset, i == self.focus, view);
+            *self.orientation.get_ref(&mut offset) += self.orientation
+                .get(&child.size);
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use event::{MouseButton, MouseEvent};
+    use views::EditView;
+
+    fn mouse(y: usize, event: MouseEvent) -> Event {
+        Event::Mouse {
+            position: Vec2::new(0, y),
+            event: event,
+        }
+    }
+
+    #[test]
+    fn test_mouse_focus() {
+        let mut layout = LinearLayout::vertical()
+            .child(EditView::new())
+            .child(EditView::new());
+        layout.layout(Vec2::new(10, 2));
+        assert!(layout.take_focus(direction::Direction::front()));
+
+        // Drags follow the focus, clicks go to the view under the mouse.
+        let hold = MouseEvent::Hold(MouseButton::Left);
+        layout.on_event(mouse(1, hold));
+        assert_eq!(layout.focus, 0);
+
+        let press = MouseEvent::Press(MouseButton::Left);
+        assert!(layout.on_event(mouse(1, press)).is_consumed());
+        assert_eq!(layout.focus, 1);
+
+        // Nothing below the last child.
+        layout.on_event(mouse(2, press));
+        assert_eq!(layout.focus, 1);
+    }
 }
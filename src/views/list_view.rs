@@ -5,12 +5,13 @@ use direction;
 use event::{Callback, Event, EventResult, Key};
 
 use std::any::Any;
+use std::cell::Cell;
 use std::rc::Rc;
 
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
 use view::ScrollBase;
-use view::Selector;
+use view::{FocusContainer, Selector, ViewInfo, ViewPath};
 use view::View;
 
 /// Represents a child from a [`ListView`].
@@ -46,6 +47,9 @@ pub struct ListView {
     focus: usize,
     // This callback is called when the selection is changed.
     on_select: Option<Rc<Fn(&mut Cursive, &String)>>,
+
+    last_offset: Cell<Vec2>,
+    last_size: Vec2,
 }
 
 new_default!(ListView);
@@ -58,6 +62,8 @@ impl ListView {
             scrollbase: ScrollBase::new(),
             focus: 0,
             on_select: None,
+            last_offset: Cell::new(Vec2::zero()),
+            last_size: Vec2::zero(),
         }
     }
 
@@ -188,15 +194,30 @@ impl ListView {
         } else {
             return EventResult::Ignored;
         };
-        self.focus = i;
-        self.scrollbase.scroll_to(self.focus);
+        self.set_focus(i)
+    }
 
+    // Moves the focus to the given row, and runs `on_select`.
+    fn set_focus(&mut self, i: usize) -> EventResult {
+        self.change_focus(i).and(self.on_select_result())
+    }
+
+    fn on_select_result(&self) -> EventResult {
         EventResult::Consumed(self.on_select.clone().map(|cb| {
-            let i = self.focus();
-            let focused_string = String::from(self.children[i].label());
-            Callback::from_fn(move |s| cb(s, &focused_string))
+            let label = String::from(self.children[self.focus].label());
+            Callback::from_fn(move |s| cb(s, &label))
         }))
     }
+
+    // Width of the label column, without the space after it.
+    fn label_width(&self) -> usize {
+        self.children
+            .iter()
+            .map(ListChild::label)
+            .map(UnicodeWidthStr::width)
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 fn try_focus((i, child): (usize, &mut ListChild),
@@ -215,18 +236,57 @@ fn try_focus((i, child): (usize, &mut ListChild),
     }
 }
 
+impl FocusContainer for ListView {
+    type Child = usize;
+
+    fn last_offset(&self) -> Vec2 {
+        self.last_offset.get()
+    }
+
+    fn child_at(&self, position: Vec2) -> Option<usize> {
+        if position.x >= self.last_size.x ||
+           position.y >= self.scrollbase.view_height {
+            return None;
+        }
+
+        let i = self.scrollbase.start_line + position.y;
+        match self.children.get(i) {
+            Some(&ListChild::Row(..)) => Some(i),
+            _ => None,
+        }
+    }
+
+    fn focused_child(&self) -> usize {
+        self.focus
+    }
+
+    fn set_focused_child(&mut self, i: usize) {
+        self.focus = i;
+        self.scrollbase.scroll_to(i);
+    }
+
+    fn child_take_focus(&mut self, i: usize, source: direction::Direction)
+                        -> bool {
+        try_focus((i, &mut self.children[i]), source).is_some()
+    }
+
+    fn child_on_event(&mut self, i: usize, event: Event) -> EventResult {
+        match self.children[i] {
+            ListChild::Row(_, ref mut view) => view.on_event(event),
+            ListChild::Delimiter => EventResult::Ignored,
+        }
+    }
+}
+
 impl View for ListView {
     fn draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
+
         if self.children.is_empty() {
             return;
         }
 
-        let offset = self.children
-            .iter()
-            .map(ListChild::label)
-            .map(UnicodeWidthStr::width)
-            .max()
-            .unwrap_or(0) + 1;
+        let offset = self.label_width() + 1;
 
         debug!("Offset: {}", offset);
         self.scrollbase.draw(printer, |printer, i| match self.children[i] {
@@ -240,12 +300,7 @@ impl View for ListView {
 
     fn required_size(&mut self, req: Vec2) -> Vec2 {
         // We'll show 2 columns: the labels, and the views.
-        let label_width = self.children
-            .iter()
-            .map(ListChild::label)
-            .map(UnicodeWidthStr::width)
-            .max()
-            .unwrap_or(0);
+        let label_width = self.label_width();
 
         let view_size = self.children
             .iter_mut()
@@ -262,15 +317,11 @@ impl View for ListView {
     }
 
     fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
         self.scrollbase.set_heights(size.y, self.children.len());
 
         // We'll show 2 columns: the labels, and the views.
-        let label_width = self.children
-            .iter()
-            .map(ListChild::label)
-            .map(UnicodeWidthStr::width)
-            .max()
-            .unwrap_or(0);
+        let label_width = self.label_width();
 
         let spacing = 1;
         let scrollbar_width = if self.children.len() > size.y { 2 } else { 0 };
@@ -290,6 +341,16 @@ impl View for ListView {
             return EventResult::Ignored;
         }
 
+        if let Event::Mouse { position, event } = event {
+            let focus = self.focus;
+            if let Some(result) = self.route_mouse_event(position, event) {
+                if self.focus == focus {
+                    return result;
+                }
+                return result.and(self.on_select_result());
+            }
+        }
+
         if let ListChild::Row(_, ref mut view) = self.children[self.focus] {
             let result = view.on_event(event.clone());
             if result.is_consumed() {
@@ -349,12 +410,47 @@ impl View for ListView {
 
     fn call_on_any<'a>(&mut self, selector: &Selector,
                        mut callback: Box<FnMut(&mut Any) + 'a>) {
+        if let Selector::Path(path) = *selector {
+            match path.split_first() {
+                None => callback(self),
+                Some((i, rest)) => {
+                    if let Some(view) = self.children
+                           .get_mut(i)
+                           .and_then(ListChild::view) {
+                        view.call_on_any(&Selector::Path(&rest), callback);
+                    }
+                }
+            }
+            return;
+        }
+
         for view in self.children.iter_mut().filter_map(ListChild::view) {
             view.call_on_any(selector, Box::new(|any| callback(any)));
         }
     }
 
     fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        if let Selector::Path(path) = *selector {
+            let (i, rest) = match path.split_first() {
+                Some(turn) => turn,
+                None if self.take_focus(direction::Direction::none()) => {
+                    return Ok(())
+                }
+                None => return Err(()),
+            };
+            let found = match self.children
+                      .get_mut(i)
+                      .and_then(ListChild::view) {
+                Some(view) => view.focus_view(&Selector::Path(&rest)).is_ok(),
+                None => false,
+            };
+            if !found {
+                return Err(());
+            }
+            self.focus = i;
+            return Ok(());
+        }
+
         if let Some(i) = self.children
                .iter_mut()
                .enumerate()
@@ -369,4 +465,55 @@ impl View for ListView {
             Err(())
         }
     }
+
+    fn focused_id(&self) -> Option<String> {
+        self.children
+            .get(self.focus)
+            .and_then(|child| match *child {
+                          ListChild::Row(_, ref view) => view.focused_id(),
+                          ListChild::Delimiter => None,
+                      })
+    }
+
+    fn focused_path(&self) -> ViewPath {
+        let mut path = ViewPath::new();
+        if let Some(&ListChild::Row(_, ref view)) =
+            self.children.get(self.focus) {
+            path = view.focused_path();
+            path.path.insert(0, self.focus);
+        }
+        path
+    }
+
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        path.split_first().and_then(|(i, rest)| match self.children.get(i) {
+                                        Some(&ListChild::Row(_, ref view)) => {
+                                            view.id_at(&rest)
+                                        }
+                                        _ => None,
+                                    })
+    }
+
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        let mut info = ViewInfo::new::<Self>(size);
+
+        // Same columns as in `layout`.
+        let offset = self.label_width() + 1;
+        let scrollbar_width = if self.children.len() > size.y { 2 } else { 0 };
+        let width = size.x.saturating_sub(offset + scrollbar_width);
+
+        let start = self.scrollbase.start_line;
+        for (i, child) in self.children
+                .iter()
+                .enumerate()
+                .skip(start)
+                .take(size.y) {
+            if let ListChild::Row(_, ref view) = *child {
+                info.add_child(Vec2::new(offset, i - start),
+                               i == self.focus,
+                               view.inspect(Vec2::new(width, 1)));
+            }
+        }
+        info
+    }
 }
@@ -5,9 +5,10 @@ use Printer;
 use With;
 use align::Align;
 use event::{Callback, Event, EventResult, Key};
-use menu::{MenuItem, MenuTree};
+use menu::{Checkable, MenuItem, MenuTree};
 use std::cmp::min;
 use std::rc::Rc;
+use theme::ColorStyle;
 
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
@@ -27,9 +28,14 @@ pub struct MenuPopup {
 impl MenuPopup {
     /// Creates a new `MenuPopup` using the given menu tree.
     pub fn new(menu: Rc<MenuTree>) -> Self {
+        // Start on the first item that can actually be selected.
+        let focus = menu.children
+            .iter()
+            .position(MenuItem::is_selectable)
+            .unwrap_or(0);
         MenuPopup {
             menu: menu,
-            focus: 0,
+            focus: focus,
             scrollbase: ScrollBase::new().scrollbar_offset(1).right_padding(0),
             align: Align::top_left(),
             on_dismiss: None,
@@ -52,37 +58,77 @@ impl MenuPopup {
     fn item_width(item: &MenuItem) -> usize {
         match *item {
             MenuItem::Delimiter => 1,
-            MenuItem::Leaf(ref title, _) => title.width(),
+            MenuItem::Leaf { ref label, ref shortcut, .. } => {
+                // Leave 2 cells between the label and the shortcut.
+                label.width() +
+                shortcut.as_ref()
+                    .map_or(0, |shortcut| shortcut.to_string().width() + 2)
+            }
             MenuItem::Subtree(ref title, _) => title.width() + 3,
         }
     }
 
+    // Width of the check marker column, if any item needs one.
+    fn marker_width(&self) -> usize {
+        if self.menu.has_checkables() { 4 } else { 0 }
+    }
+
+    fn max_item_width(&self) -> usize {
+        self.marker_width() +
+        self.menu
+            .children
+            .iter()
+            .map(Self::item_width)
+            .max()
+            .unwrap_or(1)
+    }
+
+    fn has_selectable(&self) -> bool {
+        self.menu.children.iter().any(MenuItem::is_selectable)
+    }
+
     fn scroll_up(&mut self, mut n: usize, cycle: bool) {
+        if !self.has_selectable() {
+            return;
+        }
+
+        // Don't stop on a disabled item when reaching the end.
+        let mut last = self.focus;
         while n > 0 {
             if self.focus > 0 {
                 self.focus -= 1;
             } else if cycle {
                 self.focus = self.menu.children.len() - 1;
             } else {
+                self.focus = last;
                 break;
             }
 
-            if !self.menu.children[self.focus].is_delimiter() {
+            if self.menu.children[self.focus].is_selectable() {
+                last = self.focus;
                 n -= 1;
             }
         }
     }
 
     fn scroll_down(&mut self, mut n: usize, cycle: bool) {
+        if !self.has_selectable() {
+            return;
+        }
+
+        // Don't stop on a disabled item when reaching the end.
+        let mut last = self.focus;
         while n > 0 {
             if self.focus + 1 < self.menu.children.len() {
                 self.focus += 1;
             } else if cycle {
                 self.focus = 0;
             } else {
+                self.focus = last;
                 break;
             }
-            if !self.menu.children[self.focus].is_delimiter() {
+            if self.menu.children[self.focus].is_selectable() {
+                last = self.focus;
                 n -= 1;
             }
         }
@@ -139,13 +185,7 @@ impl MenuPopup {
 
     fn make_subtree_cb(&self, tree: &Rc<MenuTree>) -> EventResult {
         let tree = tree.clone();
-        let max_width = 4 +
-                        self.menu
-                            .children
-                            .iter()
-                            .map(Self::item_width)
-                            .max()
-                            .unwrap_or(1);
+        let max_width = 4 + self.max_item_width();
         let offset = Vec2::new(max_width, self.focus);
         let action_cb = self.on_action.clone();
 
@@ -187,9 +227,10 @@ impl View for MenuPopup {
         // to integrate horizontal delimiters in the frame.
         let size = printer.size - (0, 2);
         let printer = printer.sub_printer(Vec2::new(0, 1), size, true);
+        let marker_width = self.marker_width();
         self.scrollbase.draw(&printer, |printer, i| {
-            printer.with_selection(i == self.focus, |printer| {
-                let item = &self.menu.children[i];
+            let item = &self.menu.children[i];
+            let draw_item = |printer: &Printer| {
                 match *item {
                     MenuItem::Delimiter => {
                         printer.print_hdelim((0, 0), printer.size.x)
@@ -199,32 +240,52 @@ impl View for MenuPopup {
                             return;
                         }
                         printer.print_hline((1, 0), printer.size.x - 2, " ");
-                        printer.print((2, 0), label);
+                        printer.print((2 + marker_width, 0), label);
                         printer.print((printer.size.x.saturating_sub(4), 0),
                                       ">>");
                     }
-                    MenuItem::Leaf(ref label, _) => {
+                    MenuItem::Leaf {
+                        ref label,
+                        ref shortcut,
+                        ref checkable,
+                        ..
+                    } => {
                         if printer.size.x < 2 {
                             return;
                         }
                         printer.print_hline((1, 0), printer.size.x - 2, " ");
-                        printer.print((2, 0), label);
+                        if let Some(ref checkable) = *checkable {
+                            let marker = match (checkable,
+                                                checkable.is_checked()) {
+                                (&Checkable::Checkbox(_), true) => "[X]",
+                                (&Checkable::Checkbox(_), false) => "[ ]",
+                                (&Checkable::Radio(_, _), true) => "(*)",
+                                (&Checkable::Radio(_, _), false) => "( )",
+                            };
+                            printer.print((2, 0), marker);
+                        }
+                        printer.print((2 + marker_width, 0), label);
+                        if let Some(ref shortcut) = *shortcut {
+                            let shortcut = shortcut.to_string();
+                            let x = printer.size.x
+                                .saturating_sub(shortcut.width() + 2);
+                            printer.print((x, 0), &shortcut);
+                        }
                     }
                 }
+            };
 
-            });
+            if item.is_enabled() {
+                printer.with_selection(i == self.focus, draw_item);
+            } else {
+                printer.with_color(ColorStyle::Secondary, draw_item);
+            }
         });
     }
 
     fn required_size(&mut self, req: Vec2) -> Vec2 {
         // We can't really shrink our items here, so it's not flexible.
-        let w = 4 +
-                self.menu
-                    .children
-                    .iter()
-                    .map(Self::item_width)
-                    .max()
-                    .unwrap_or(1);
+        let w = 4 + self.max_item_width();
         let h = 2 + self.menu.children.len();
 
 
@@ -251,12 +312,19 @@ impl View for MenuPopup {
             Event::Key(Key::Down) => self.scroll_down(1, true),
             Event::Key(Key::PageDown) => self.scroll_down(5, false),
 
-            Event::Key(Key::Home) => self.focus = 0,
+            Event::Key(Key::Home) => {
+                self.focus = self.menu
+                    .children
+                    .iter()
+                    .position(MenuItem::is_selectable)
+                    .unwrap_or(0)
+            }
             Event::Key(Key::End) => {
                 self.focus = self.menu
                     .children
-                    .len()
-                    .saturating_sub(1)
+                    .iter()
+                    .rposition(MenuItem::is_selectable)
+                    .unwrap_or(0)
             }
 
             Event::Key(Key::Right) if self.menu.children[self.focus]
@@ -269,12 +337,13 @@ impl View for MenuPopup {
 
                        };
             }
-            Event::Key(Key::Enter) if !self.menu.children[self.focus]
-                                           .is_delimiter() => {
-                return match self.menu.children[self.focus] {
-                           MenuItem::Leaf(_, ref cb) => {
+            Event::Key(Key::Enter) if self.menu.children[self.focus]
+                                          .is_selectable() => {
+                let item = &self.menu.children[self.focus];
+                return match *item {
+                           MenuItem::Leaf { .. } => {
 
-                    let cb = cb.clone();
+                    let cb = item.activate().unwrap();
                     let action_cb = self.on_action.clone();
                     EventResult::with_cb(move |s| {
                         // Remove ourselves from the face of the earth
@@ -307,3 +376,54 @@ impl View for MenuPopup {
                                     self.menu.children.len());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn popup(enabled: &[bool]) -> MenuPopup {
+        let mut tree = MenuTree::new();
+        for (i, &enabled) in enabled.iter().enumerate() {
+            tree.add_leaf(i.to_string(), |_| ());
+            tree.children[i].set_enabled(enabled);
+        }
+        let mut popup = MenuPopup::new(Rc::new(tree));
+        popup.layout(Vec2::new(10, 10));
+        popup
+    }
+
+    fn focus_after(popup: &mut MenuPopup, key: Key) -> usize {
+        popup.on_event(Event::Key(key));
+        popup.focus
+    }
+
+    #[test]
+    fn test_skip_disabled() {
+        let mut popup = popup(&[false, true, false, true, false]);
+        assert_eq!(popup.focus, 1);
+
+        assert_eq!(focus_after(&mut popup, Key::Down), 3);
+        assert_eq!(focus_after(&mut popup, Key::Down), 1);
+        assert_eq!(focus_after(&mut popup, Key::Up), 3);
+        assert_eq!(focus_after(&mut popup, Key::Up), 1);
+        assert_eq!(focus_after(&mut popup, Key::PageDown), 3);
+        assert_eq!(focus_after(&mut popup, Key::PageUp), 1);
+        assert_eq!(focus_after(&mut popup, Key::End), 3);
+        assert_eq!(focus_after(&mut popup, Key::Home), 1);
+    }
+
+    #[test]
+    fn test_all_disabled() {
+        let mut popup = popup(&[false, false, false]);
+        assert_eq!(popup.focus, 0);
+
+        for &key in &[Key::Down, Key::Up, Key::PageDown, Key::PageUp,
+                      Key::End, Key::Home] {
+            assert_eq!(focus_after(&mut popup, key), 0);
+        }
+        match popup.on_event(Event::Key(Key::Enter)) {
+            EventResult::Ignored => (),
+            _ => panic!("a disabled leaf was activated"),
+        }
+    }
+}
@@ -6,7 +6,7 @@ use menu::MenuTree;
 
 use std::rc::Rc;
 
-use theme::ColorStyle;
+use theme::{ColorStyle, Effect};
 use unicode_width::UnicodeWidthStr;
 use vec::Vec2;
 use view::{Position, View};
@@ -32,10 +32,21 @@ enum State {
 /// The [`Cursive`] root already includes a menubar
 /// that you just need to configure.
 ///
+/// # Mnemonics
+///
+/// A `&` before a letter or a digit in an item title marks it as the
+/// mnemonic: the title `"&File"` is shown as `File` with an underlined `F`,
+/// and `<Alt-F>` opens it from anywhere. Other `&` are shown as they are,
+/// and `&&` can be used for a literal `&` before a letter.
+///
+/// Items can be found by their title with or without the markers.
+///
 /// [`Cursive`]: ../struct.Cursive.html#method.menubar
 pub struct Menubar {
     /// Menu items in this menubar.
     menus: Vec<(String, Rc<MenuTree>)>,
+    /// Byte offset of the mnemonic in each title, if any.
+    mnemonics: Vec<Option<usize>>,
     /// TODO: move this out of this view.
     pub autohide: bool,
     focus: usize,
@@ -51,6 +62,7 @@ impl Menubar {
     pub fn new() -> Self {
         Menubar {
             menus: Vec::new(),
+            mnemonics: Vec::new(),
             autohide: true,
             state: State::Inactive,
             focus: 0,
@@ -84,16 +96,44 @@ impl Menubar {
     /// Insert a new item at the given position.
     pub fn insert_subtree(&mut self, i: usize, title: &str, menu: MenuTree)
                           -> &mut Self {
-        self.menus.insert(i, (title.to_string(), Rc::new(menu)));
+        let (title, mnemonic) = parse_mnemonic(title);
+        self.menus.insert(i, (title, Rc::new(menu)));
+        self.mnemonics.insert(i, mnemonic);
         self
     }
 
     /// Removes all menu items from this menubar.
     pub fn clear(&mut self) {
         self.menus.clear();
+        self.mnemonics.clear();
         self.focus = 0;
     }
 
+    /// Returns the mnemonic character of the item at the given position.
+    pub fn mnemonic(&self, i: usize) -> Option<char> {
+        self.mnemonics[i].and_then(|j| self.menus[i].0[j..].chars().next())
+    }
+
+    /// Returns the position of the item with the given mnemonic.
+    ///
+    /// The comparison ignores case.
+    pub fn find_mnemonic(&self, c: char) -> Option<usize> {
+        (0..self.menus.len()).find(|&i| {
+            self.mnemonic(i)
+                .map_or(false, |m| m.to_lowercase().eq(c.to_lowercase()))
+        })
+    }
+
+    /// Looks for a leaf with the given shortcut in all the menus.
+    ///
+    /// Returns the callback of the first match.
+    pub fn find_shortcut(&self, event: &Event) -> Option<Callback> {
+        self.menus
+            .iter()
+            .filter_map(|&(_, ref tree)| tree.find_shortcut(event))
+            .next()
+    }
+
     /// Returns the number of items in this menubar.
     pub fn len(&self) -> usize {
         self.menus.len()
@@ -111,31 +151,94 @@ impl Menubar {
         self.menus.get_mut(i).map(|&mut (_, ref mut tree)| Rc::make_mut(tree))
     }
 
+    /// Returns an iterator on the items in this menubar.
+    ///
+    /// Each item is given with its label and its menu tree.
+    pub fn subtrees<'a>(&'a self)
+                        -> Box<Iterator<Item = (&'a str, &'a MenuTree)> + 'a> {
+        Box::new(self.menus
+                     .iter()
+                     .map(|&(ref label, ref tree)| (label.as_str(), &**tree)))
+    }
+
     /// Looks for an item with the given label.
     pub fn find_subtree(&mut self, label: &str) -> Option<&mut MenuTree> {
         // Look for the menu with the correct label,
         // then call Rc::make_mut on the tree.
         // If another Rc on this tree existed, this will clone
         // the tree and keep the forked version.
-        self.menus
-            .iter_mut()
-            .find(|&&mut (ref l, _)| l == label)
-            .map(|&mut (_, ref mut tree)| Rc::make_mut(tree))
+        self.find_position(label)
+            .map(move |i| Rc::make_mut(&mut self.menus[i].1))
     }
 
     /// Returns the position of the item with the given label.
     ///
     /// Returns `None` if no such label was found.
     pub fn find_position(&mut self, label: &str) -> Option<usize> {
-        self.menus.iter().position(|&(ref l, _)| l == label)
+        let (stripped, _) = parse_mnemonic(label);
+        self.menus
+            .iter()
+            .position(|&(ref l, _)| l == label || *l == stripped)
     }
 
     /// Remove the item at the given position.
     pub fn remove(&mut self, i: usize) {
         self.menus.remove(i);
+        self.mnemonics.remove(i);
+    }
+
+    // Opens the popup for the focused item.
+    fn open_focused(&mut self) -> EventResult {
+        // First, we need a new Rc to send the callback,
+        // since we don't know when it will be called.
+        let menu = self.menus[self.focus].1.clone();
+        self.state = State::Submenu;
+        let offset = (self.menus[..self.focus]
+                          .iter()
+                          .map(|&(ref title, _)| title.width() + 2)
+                          .fold(0, |a, b| a + b),
+                      if self.autohide { 1 } else { 0 });
+        // Since the closure will be called multiple times,
+        // we also need a new Rc on every call.
+        EventResult::with_cb(move |s| show_child(s, offset, menu.clone()))
     }
 }
 
+/// Strips the `&` markers from `title`.
+///
+/// Returns the title to display, and the byte offset of the first
+/// alphanumeric character following a single `&`.
+fn parse_mnemonic(title: &str) -> (String, Option<usize>) {
+    let mut result = String::with_capacity(title.len());
+    let mut mnemonic = None;
+    let mut chars = title.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '&' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.peek().cloned() {
+            Some('&') => {
+                chars.next();
+                result.push('&');
+            }
+            Some(c) if c.is_alphanumeric() => {
+                chars.next();
+                if mnemonic.is_none() {
+                    mnemonic = Some(result.len());
+                }
+                result.push(c);
+            }
+            // Not a marker: keep it, and the next character, as they are.
+            _ => result.push('&'),
+        }
+    }
+
+    (result, mnemonic)
+}
+
 fn show_child(s: &mut Cursive, offset: (usize, usize), menu: Rc<MenuTree>) {
     // Adds a new layer located near the item title with the menu popup.
     // Also adds two key callbacks on this new view, to handle `left` and
@@ -192,6 +295,14 @@ impl View for Menubar {
                            (i == self.focus);
             printer.with_selection(selected, |printer| {
                 printer.print((offset, 0), &format!(" {} ", title));
+                if let Some(j) = self.mnemonics[i] {
+                    if let Some(c) = title[j..].chars().next() {
+                        let x = offset + 1 + title[..j].width();
+                        printer.with_effect(Effect::Underline, |printer| {
+                            printer.print((x, 0), &c.to_string());
+                        });
+                    }
+                }
                 offset += title.width() + 2;
             });
         }
@@ -219,23 +330,17 @@ impl View for Menubar {
             }
             Event::Key(Key::Down) |
             Event::Key(Key::Enter) => {
-                // First, we need a new Rc to send the callback,
-                // since we don't know when it will be called.
-                let menu = self.menus[self.focus].1.clone();
-                self.state = State::Submenu;
-                let offset =
-                    (self.menus[..self.focus]
-                         .iter()
-                         .map(|&(ref title, _)| title.width() + 2)
-                         .fold(0, |a, b| a + b),
-                     if self.autohide { 1 } else { 0 });
-                // Since the closure will be called multiple times,
-                // we also need a new Rc on every call.
-                return EventResult::with_cb(move |s| {
-                                                show_child(s,
-                                                           offset,
-                                                           menu.clone())
-                                            });
+                return self.open_focused();
+            }
+            Event::Char(c) |
+            Event::AltChar(c) => {
+                match self.find_mnemonic(c) {
+                    Some(i) => {
+                        self.focus = i;
+                        return self.open_focused();
+                    }
+                    None => return EventResult::Ignored,
+                }
             }
             _ => return EventResult::Ignored,
         }
@@ -261,3 +366,35 @@ impl View for Menubar {
         Vec2::new(width, 1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Menubar, parse_mnemonic};
+    use menu::MenuTree;
+
+    #[test]
+    fn test_parse_mnemonic() {
+        assert_eq!(parse_mnemonic("File"), ("File".to_string(), None));
+        assert_eq!(parse_mnemonic("&File"), ("File".to_string(), Some(0)));
+        assert_eq!(parse_mnemonic("E&xit"), ("Exit".to_string(), Some(1)));
+        assert_eq!(parse_mnemonic("A&&B"), ("A&B".to_string(), None));
+        assert_eq!(parse_mnemonic("&A && &B"), ("A & B".to_string(), Some(0)));
+        assert_eq!(parse_mnemonic("Tools & Options"),
+                   ("Tools & Options".to_string(), None));
+        assert_eq!(parse_mnemonic("R & &D"), ("R & D".to_string(), Some(4)));
+        assert_eq!(parse_mnemonic("&"), ("&".to_string(), None));
+    }
+
+    #[test]
+    fn test_find_subtree() {
+        let mut menubar = Menubar::new();
+        menubar.add_subtree("&File", MenuTree::new());
+        menubar.add_subtree("Tools & Options", MenuTree::new());
+
+        assert_eq!(menubar.find_position("File"), Some(0));
+        assert_eq!(menubar.find_position("&File"), Some(0));
+        assert_eq!(menubar.find_position("Tools & Options"), Some(1));
+        assert!(menubar.find_subtree("Tools & Options").is_some());
+        assert_eq!(menubar.mnemonic(1), None);
+    }
+}
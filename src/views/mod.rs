@@ -39,12 +39,17 @@ mod box_view;
 mod button;
 mod canvas;
 mod checkbox;
+mod command_palette;
+mod context_menu;
 mod dialog;
 mod dummy;
 mod edit_view;
+mod focus_scope;
+mod grid_layout;
 mod id_view;
 mod on_event_view;
 mod layer;
+mod lazy_select_view;
 mod linear_layout;
 mod list_view;
 mod menubar;
@@ -54,23 +59,34 @@ mod progress_bar;
 mod radio;
 mod select_view;
 mod slider_view;
+mod split_view;
+mod status_bar;
 mod shadow_view;
 mod sized_view;
 mod stack_view;
 mod text_area;
 mod text_view;
+mod themed_view;
 mod tracked_view;
 
+#[cfg(test)]
+pub mod tests;
+
 pub use self::box_view::BoxView;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
 pub use self::checkbox::Checkbox;
+pub use self::command_palette::{Command, CommandPalette};
+pub use self::context_menu::ContextMenu;
 pub use self::dialog::Dialog;
 pub use self::dummy::DummyView;
 pub use self::edit_view::EditView;
+pub use self::focus_scope::FocusScope;
+pub use self::grid_layout::{ColumnWidth, GridLayout};
 pub use self::id_view::{IdView, ViewRef};
 pub use self::on_event_view::OnEventView;
 pub use self::layer::Layer;
+pub use self::lazy_select_view::{LazySelectView, SelectSource};
 pub use self::linear_layout::LinearLayout;
 pub use self::list_view::{ListChild, ListView};
 pub use self::menu_popup::MenuPopup;
@@ -82,7 +98,10 @@ pub use self::select_view::SelectView;
 pub use self::shadow_view::ShadowView;
 pub use self::sized_view::SizedView;
 pub use self::slider_view::SliderView;
+pub use self::split_view::{Pane, SplitView};
+pub use self::status_bar::StatusBar;
 pub use self::stack_view::StackView;
 pub use self::text_area::TextArea;
 pub use self::text_view::TextView;
+pub use self::themed_view::ThemedView;
 pub use self::tracked_view::TrackedView;
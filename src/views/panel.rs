@@ -1,16 +1,40 @@
 use Printer;
+use With;
+use theme::BorderStyle;
 use vec::Vec2;
-use view::{View, ViewWrapper};
+use view::{View, ViewInfo, ViewWrapper};
 
 /// Draws a border around a wrapped view.
 pub struct Panel<V: View> {
     view: V,
+    // Overrides the theme's border style.
+    border_style: Option<BorderStyle>,
 }
 
 impl<V: View> Panel<V> {
     /// Creates a new panel around the given view.
     pub fn new(view: V) -> Self {
-        Panel { view: view }
+        Panel {
+            view: view,
+            border_style: None,
+        }
+    }
+
+    /// Sets the border style, ignoring the theme's.
+    pub fn set_border_style(&mut self, style: BorderStyle) {
+        self.border_style = Some(style);
+    }
+
+    /// Sets the border style, ignoring the theme's.
+    ///
+    /// Chainable variant.
+    pub fn border_style(self, style: BorderStyle) -> Self {
+        self.with(|s| s.set_border_style(style))
+    }
+
+    /// Uses the theme's border style again.
+    pub fn reset_border_style(&mut self) {
+        self.border_style = None;
     }
 }
 
@@ -26,7 +50,8 @@ impl<V: View> ViewWrapper for Panel<V> {
     }
 
     fn wrap_draw(&self, printer: &Printer) {
-        printer.print_box((0, 0), printer.size, true);
+        let style = self.border_style.unwrap_or(printer.theme.borders);
+        printer.print_styled_box((0, 0), printer.size, true, &style);
         self.view.draw(&printer.sub_printer((1, 1),
                                             printer.size.saturating_sub((2, 2)),
                                             true));
@@ -35,4 +60,9 @@ impl<V: View> ViewWrapper for Panel<V> {
     fn wrap_layout(&mut self, size: Vec2) {
         self.view.layout(size.saturating_sub((2, 2)));
     }
+
+    fn wrap_inspect(&self, size: Vec2) -> ViewInfo {
+        let child = self.view.inspect(size.saturating_sub((2, 2)));
+        ViewInfo::new::<Self>(size).child(Vec2::new(1, 1), true, child)
+    }
 }
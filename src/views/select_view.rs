@@ -46,6 +46,21 @@ use views::MenuPopup;
 /// # }
 ///
 /// ```
+///
+/// # Multi-selection
+///
+/// With [`multi`], each item gets a check marker and any number of them can
+/// be chosen at once:
+///
+/// * `<Space>` toggles the focused item.
+/// * `<Shift>` with a movement key selects every item between the last
+///   toggled item and the new focus. Moving again replaces this range.
+/// * `<Ctrl-A>` selects all items, `<Ctrl-D>` clears the selection.
+///
+/// The chosen values are available through [`selections`].
+///
+/// [`multi`]: #method.multi
+/// [`selections`]: #method.selections
 pub struct SelectView<T = String> {
     items: Vec<Item<T>>,
     enabled: bool,
@@ -57,9 +72,17 @@ pub struct SelectView<T = String> {
     on_submit: Option<Rc<Fn(&mut Cursive, &T)>>,
     // This callback is called when the selection is changed.
     on_select: Option<Rc<Fn(&mut Cursive, &T)>>,
+    // This callback is called when the set of chosen items changes.
+    on_selection_change: Option<Rc<Fn(&mut Cursive, &[Rc<T>])>>,
     align: Align,
     // `true` if we show a one-line view, with popup on selection.
     popup: bool,
+    // `true` if several items can be chosen at once.
+    multi: bool,
+    // Start of the range for shift-selection.
+    anchor: Option<usize>,
+    // Chosen items before the current shift-selection started.
+    range_base: Option<Vec<bool>>,
     // We need the last offset to place the popup window
     // We "cache" it during the draw, so we need interior mutability.
     last_offset: Cell<Vec2>,
@@ -82,8 +105,12 @@ impl<T: 'static> SelectView<T> {
             scrollbase: ScrollBase::new(),
             on_select: None,
             on_submit: None,
+            on_selection_change: None,
             align: Align::top_left(),
             popup: false,
+            multi: false,
+            anchor: None,
+            range_base: None,
             last_offset: Cell::new(Vec2::zero()),
             last_size: Vec2::zero(),
         }
@@ -101,6 +128,30 @@ impl<T: 'static> SelectView<T> {
         self.popup = popup;
     }
 
+    /// Allows several items to be selected at once.
+    ///
+    /// Chainable variant.
+    pub fn multi(self) -> Self {
+        self.with(|s| s.set_multi(true))
+    }
+
+    /// Enables or disables multi-selection.
+    ///
+    /// Has no effect on popup select views.
+    ///
+    /// Disabling it clears the current multi-selection.
+    pub fn set_multi(&mut self, multi: bool) {
+        self.multi = multi;
+        if !multi {
+            self.select_none();
+        }
+    }
+
+    /// Returns `true` if several items can be selected at once.
+    pub fn is_multi(&self) -> bool {
+        self.multi
+    }
+
     /// Disables this view.
     ///
     /// A disabled view cannot be selected.
@@ -148,6 +199,27 @@ impl<T: 'static> SelectView<T> {
         self.with(|s| s.set_on_select(cb))
     }
 
+    /// Sets a callback to be used when the set of chosen items changes.
+    ///
+    /// Only called in multi-selection mode, when the user changes the
+    /// selection. All chosen values will be given to the callback.
+    pub fn set_on_selection_change<F>(&mut self, cb: F)
+    where
+        F: Fn(&mut Cursive, &[Rc<T>]) + 'static,
+    {
+        self.on_selection_change = Some(Rc::new(cb));
+    }
+
+    /// Sets a callback to be used when the set of chosen items changes.
+    ///
+    /// Chainable variant.
+    pub fn on_selection_change<F>(self, cb: F) -> Self
+    where
+        F: Fn(&mut Cursive, &[Rc<T>]) + 'static,
+    {
+        self.with(|s| s.set_on_selection_change(cb))
+    }
+
     /// Sets a callback to be used when `<Enter>` is pressed.
     ///
     /// The item currently selected will be given to the callback.
@@ -204,15 +276,74 @@ impl<T: 'static> SelectView<T> {
         self.items[self.focus()].value.clone()
     }
 
+    /// Returns the values of all chosen items, in list order.
+    ///
+    /// Only items toggled in multi-selection mode are included.
+    pub fn selections(&self) -> Vec<Rc<T>> {
+        self.items
+            .iter()
+            .filter(|item| item.selected)
+            .map(|item| item.value.clone())
+            .collect()
+    }
+
+    /// Returns the ids of all chosen items, in increasing order.
+    pub fn selected_ids(&self) -> Vec<usize> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|&(_, item)| item.selected)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Returns `true` if the item at the given position is chosen.
+    pub fn is_item_selected(&self, id: usize) -> bool {
+        self.items[id].selected
+    }
+
+    /// Chooses or un-chooses the item at the given position.
+    pub fn set_item_selected(&mut self, id: usize, selected: bool) {
+        self.items[id].selected = selected;
+        self.anchor = Some(id);
+        self.range_base = None;
+    }
+
+    /// Toggles the item at the given position.
+    pub fn toggle_item(&mut self, id: usize) {
+        let selected = !self.items[id].selected;
+        self.set_item_selected(id, selected);
+    }
+
+    /// Chooses every item in the list.
+    pub fn select_all(&mut self) {
+        for item in &mut self.items {
+            item.selected = true;
+        }
+        self.range_base = None;
+    }
+
+    /// Clears the multi-selection.
+    pub fn select_none(&mut self) {
+        for item in &mut self.items {
+            item.selected = false;
+        }
+        self.anchor = None;
+        self.range_base = None;
+    }
+
     /// Removes all items from this view.
     pub fn clear(&mut self) {
         self.items.clear();
         self.focus.set(0);
+        self.anchor = None;
+        self.range_base = None;
     }
 
     /// Adds a item to the list, with given label and value.
     pub fn add_item<S: Into<String>>(&mut self, label: S, value: T) {
         self.items.push(Item::new(label.into(), value));
+        self.range_base = None;
     }
 
     /// Removes an item from the list.
@@ -222,6 +353,12 @@ impl<T: 'static> SelectView<T> {
         if focus >= id && focus > 0 {
             self.focus.set(focus - 1);
         }
+        self.anchor = match self.anchor {
+            Some(anchor) if anchor == id => None,
+            Some(anchor) if anchor > id => Some(anchor - 1),
+            anchor => anchor,
+        };
+        self.range_base = None;
     }
 
     /// Chainable variant of add_item
@@ -252,6 +389,15 @@ impl<T: 'static> SelectView<T> {
     }
 
     fn draw_item(&self, printer: &Printer, i: usize) {
+        let marker_width = if self.multi {
+            let marker = if self.items[i].selected { "[X] " } else { "[ ] " };
+            printer.print((0, 0), marker);
+            MARKER_WIDTH
+        } else {
+            0
+        };
+        let printer = &printer.offset((marker_width, 0), true);
+
         let l = self.items[i].label.width();
         let x = self.align.h.get_offset(l, printer.size.x);
         printer.print_hline((0, 0), x, " ");
@@ -319,6 +465,97 @@ impl<T: 'static> SelectView<T> {
         let focus = min(self.focus() + n, self.items.len().saturating_sub(1));
         self.focus.set(focus);
     }
+
+    // Chooses every item between the anchor and the focus.
+    //
+    // Items chosen by the previous range are replaced.
+    fn select_range(&mut self) {
+        let focus = self.focus();
+        let anchor = self.anchor.unwrap_or(focus);
+        let (start, end) = if anchor < focus {
+            (anchor, focus)
+        } else {
+            (focus, anchor)
+        };
+
+        if self.range_base.is_none() {
+            let base = self.items.iter().map(|item| item.selected).collect();
+            self.range_base = Some(base);
+        }
+        if let Some(ref base) = self.range_base {
+            for (item, &selected) in self.items.iter_mut().zip(base) {
+                item.selected = selected;
+            }
+        }
+
+        for item in &mut self.items[start..end + 1] {
+            item.selected = true;
+        }
+        self.anchor = Some(anchor);
+    }
+
+    fn on_multi_event(&mut self, event: &Event) -> Option<EventResult> {
+        if self.items.is_empty() {
+            return None;
+        }
+
+        // Movement keys with shift extend the selection
+        // from the anchor to the new focus.
+        let before = self.selected_ids();
+        let range_move: Option<fn(&mut Self)> = match *event {
+            Event::Shift(Key::Up) => Some(|s| s.focus_up(1)),
+            Event::Shift(Key::Down) => Some(|s| s.focus_down(1)),
+            Event::Shift(Key::PageUp) => Some(|s| s.focus_up(10)),
+            Event::Shift(Key::PageDown) => Some(|s| s.focus_down(10)),
+            Event::Shift(Key::Home) => Some(|s| s.focus.set(0)),
+            Event::Shift(Key::End) => Some(|s| {
+                let last = s.items.len() - 1;
+                s.focus.set(last)
+            }),
+            _ => None,
+        };
+
+        let moved = range_move.is_some();
+        if let Some(range_move) = range_move {
+            if self.anchor.is_none() {
+                self.anchor = Some(self.focus());
+            }
+            range_move(self);
+            let focus = self.focus();
+            self.scrollbase.scroll_to(focus);
+            self.select_range();
+        } else {
+            match *event {
+                Event::Char(' ') => {
+                    let focus = self.focus();
+                    self.toggle_item(focus);
+                }
+                Event::CtrlChar('a') => self.select_all(),
+                Event::CtrlChar('d') => self.select_none(),
+                _ => return None,
+            }
+        }
+
+        // Like plain movement keys, shift movements report the focus.
+        let select = if moved { self.select_cb() } else { None };
+        let change = if self.selected_ids() == before {
+            None
+        } else {
+            self.on_selection_change.clone().map(|cb| {
+                let selections = self.selections();
+                Callback::from_fn(move |s| cb(s, &selections))
+            })
+        };
+        Some(EventResult::Consumed(select).and(EventResult::Consumed(change)))
+    }
+
+    // Callback for `on_select`, with the focused value.
+    fn select_cb(&self) -> Option<Callback> {
+        self.on_select.clone().map(|cb| {
+            let v = self.selection();
+            Callback::from_fn(move |s| cb(s, &v))
+        })
+    }
 }
 
 impl SelectView<String> {
@@ -427,6 +664,7 @@ impl<T: 'static> View for SelectView<T> {
         if self.popup {
             Vec2::new(w + 2, 1)
         } else {
+            let w = if self.multi { w + MARKER_WIDTH } else { w };
             let h = self.items.len();
 
             let scrolling = req.y < h;
@@ -497,6 +735,12 @@ impl<T: 'static> View for SelectView<T> {
                 _ => EventResult::Ignored,
             }
         } else {
+            if self.multi {
+                if let Some(result) = self.on_multi_event(&event) {
+                    return result;
+                }
+            }
+
             match event {
                 Event::Key(Key::Up) if self.focus() > 0 => self.focus_up(1),
                 Event::Key(Key::Down)
@@ -539,10 +783,7 @@ impl<T: 'static> View for SelectView<T> {
             let focus = self.focus();
             self.scrollbase.scroll_to(focus);
 
-            EventResult::Consumed(self.on_select.clone().map(|cb| {
-                let v = self.selection();
-                Callback::from_fn(move |s| cb(s, &v))
-            }))
+            EventResult::Consumed(self.select_cb())
         }
     }
 
@@ -559,9 +800,14 @@ impl<T: 'static> View for SelectView<T> {
     }
 }
 
+// Width of the `[X] ` marker drawn in multi-selection mode.
+const MARKER_WIDTH: usize = 4;
+
 struct Item<T> {
     label: String,
     value: Rc<T>,
+    // `true` if the item is chosen in multi-selection mode.
+    selected: bool,
 }
 
 impl<T> Item<T> {
@@ -569,6 +815,112 @@ impl<T> Item<T> {
         Item {
             label: label,
             value: Rc::new(value),
+            selected: false,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(view: &mut SelectView, event: Event) {
+        assert!(view.on_event(event).is_consumed());
+    }
+
+    fn multi_view() -> SelectView {
+        let mut view = SelectView::new().multi();
+        for label in &["a", "b", "c", "d", "e", "f"] {
+            view.add_item_str(*label);
+        }
+        view.layout(Vec2::new(10, 6));
+        view
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut view = multi_view();
+        press(&mut view, Event::Char(' '));
+        assert_eq!(view.selected_ids(), vec![0]);
+
+        press(&mut view, Event::Key(Key::Down));
+        press(&mut view, Event::Char(' '));
+        assert_eq!(view.selected_ids(), vec![0, 1]);
+        assert_eq!(*view.selections()[1], "b");
+
+        press(&mut view, Event::Char(' '));
+        assert_eq!(view.selected_ids(), vec![0]);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut view = multi_view();
+        press(&mut view, Event::Char(' '));
+        for _ in 0..3 {
+            press(&mut view, Event::Shift(Key::Down));
+        }
+        assert_eq!(view.selected_ids(), vec![0, 1, 2, 3]);
+
+        // Moving back shrinks the range around the anchor.
+        press(&mut view, Event::Shift(Key::Up));
+        assert_eq!(view.selected_ids(), vec![0, 1, 2]);
+        press(&mut view, Event::Shift(Key::Home));
+        assert_eq!(view.selected_ids(), vec![0]);
+
+        // A new range keeps the items chosen before it.
+        for _ in 0..4 {
+            press(&mut view, Event::Key(Key::Down));
+        }
+        press(&mut view, Event::Char(' '));
+        press(&mut view, Event::Shift(Key::Up));
+        press(&mut view, Event::Shift(Key::Up));
+        assert_eq!(view.selected_ids(), vec![0, 2, 3, 4]);
+        press(&mut view, Event::Shift(Key::End));
+        assert_eq!(view.selected_ids(), vec![0, 4, 5]);
+    }
+
+    #[test]
+    fn test_select_all() {
+        let mut view = multi_view();
+        press(&mut view, Event::CtrlChar('a'));
+        assert_eq!(view.selected_ids(), vec![0, 1, 2, 3, 4, 5]);
+
+        press(&mut view, Event::CtrlChar('d'));
+        assert!(view.selected_ids().is_empty());
+
+        // The anchor is cleared too.
+        press(&mut view, Event::Key(Key::Down));
+        press(&mut view, Event::Shift(Key::Down));
+        assert_eq!(view.selected_ids(), vec![1, 2]);
+    }
+
+    // Returns `true` if the event gave a callback to run.
+    fn has_callback(view: &mut SelectView, event: Event) -> bool {
+        match view.on_event(event) {
+            EventResult::Consumed(cb) => cb.is_some(),
+            EventResult::Ignored => panic!("event ignored"),
+        }
+    }
+
+    #[test]
+    fn test_multi_callbacks() {
+        let mut view = multi_view().on_selection_change(|_, _| ());
+
+        // Nothing changes without a selection.
+        assert!(!has_callback(&mut view, Event::CtrlChar('d')));
+        assert!(has_callback(&mut view, Event::CtrlChar('a')));
+        assert!(!has_callback(&mut view, Event::CtrlChar('a')));
+        assert!(!has_callback(&mut view, Event::Shift(Key::End)));
+        assert!(has_callback(&mut view, Event::Char(' ')));
+
+        view.select_none();
+        assert!(has_callback(&mut view, Event::Shift(Key::Home)));
+        assert!(!has_callback(&mut view, Event::Shift(Key::Home)));
+
+        // Shift movements report the focus, like plain ones.
+        let mut view = multi_view().on_select(|_, _| ());
+        assert!(has_callback(&mut view, Event::Shift(Key::Down)));
+        assert!(has_callback(&mut view, Event::Key(Key::Down)));
+        assert!(!has_callback(&mut view, Event::Char(' ')));
+    }
+}
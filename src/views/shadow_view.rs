@@ -1,7 +1,7 @@
 use Printer;
 use theme::ColorStyle;
 use vec::Vec2;
-use view::{View, ViewWrapper};
+use view::{View, ViewInfo, ViewWrapper};
 
 /// Wrapper view that adds a shadow.
 ///
@@ -58,6 +58,13 @@ impl<T: View> ViewWrapper for ShadowView<T> {
         self.view.layout(size.saturating_sub(offset));
     }
 
+    fn wrap_inspect(&self, size: Vec2) -> ViewInfo {
+        let offset = Vec2::new(self.left_padding as usize,
+                               self.top_padding as usize);
+        let child = self.view.inspect(size.saturating_sub(self.padding()));
+        ViewInfo::new::<Self>(size).child(offset, true, child)
+    }
+
     fn wrap_draw(&self, printer: &Printer) {
 
         if printer.size.y <= self.top_padding as usize ||
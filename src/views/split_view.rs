@@ -0,0 +1,604 @@
+use Printer;
+use With;
+use direction::{Direction, Orientation};
+use event::{Event, EventResult, Key, MouseButton, MouseEvent};
+use std::any::Any;
+use std::cell::Cell;
+use std::cmp::{max, min};
+use vec::Vec2;
+use view::{FocusContainer, Selector, View, ViewInfo, ViewPath};
+
+/// Identifies one of the two panes of a [`SplitView`].
+///
+/// [`SplitView`]: struct.SplitView.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pane {
+    /// The left or top pane.
+    First,
+    /// The right or bottom pane.
+    Second,
+}
+
+impl Pane {
+    fn index(self) -> usize {
+        match self {
+            Pane::First => 0,
+            Pane::Second => 1,
+        }
+    }
+}
+
+/// Shows two views side by side, separated by a movable divider.
+///
+/// The user can move the divider by dragging it with the mouse, or with
+/// `<Ctrl>` and the arrow keys when the focused view ignores them.
+/// `<Tab>` and the arrow keys move the focus between the panes.
+/// Mouse events are only reported after `Cursive::set_mouse_capture`.
+///
+/// The split is stored as a ratio, so it can be saved and restored
+/// regardless of the screen size.
+///
+/// # Examples
+///
+/// ```
+/// # use cursive::views::{Pane, SplitView, TextView};
+/// let split = SplitView::horizontal(TextView::new("Files"),
+///                                   TextView::new("Content"))
+///     .ratio(0.3)
+///     .min_size(Pane::First, 10);
+/// ```
+pub struct SplitView {
+    orientation: Orientation,
+    children: [Child; 2],
+    focus: usize,
+
+    // Part of the available space given to the first pane.
+    ratio: f64,
+
+    // Size of the first pane, as computed by the last layout.
+    position: usize,
+    // Size available to both panes (without the divider).
+    available: usize,
+
+    last_offset: Cell<Vec2>,
+    last_size: Vec2,
+    dragging: bool,
+}
+
+struct Child {
+    view: Box<View>,
+    min_size: usize,
+    max_size: Option<usize>,
+    collapsed: bool,
+}
+
+impl Child {
+    fn new<V: View + 'static>(view: V) -> Self {
+        Child {
+            view: Box::new(view),
+            min_size: 0,
+            max_size: None,
+            collapsed: false,
+        }
+    }
+
+    // Clamps `size` within the allowed range for this pane.
+    fn clamp(&self, size: usize) -> usize {
+        let size = max(size, self.min_size);
+        match self.max_size {
+            Some(max_size) => min(size, max_size),
+            None => size,
+        }
+    }
+}
+
+impl SplitView {
+    /// Creates a new split view with the given orientation.
+    ///
+    /// An horizontal split has its panes side by side,
+    /// like a `LinearLayout::horizontal()`.
+    pub fn new<A, B>(orientation: Orientation, first: A, second: B) -> Self
+        where A: View + 'static,
+              B: View + 'static
+    {
+        SplitView {
+            orientation: orientation,
+            children: [Child::new(first), Child::new(second)],
+            focus: 0,
+            ratio: 0.5,
+            position: 0,
+            available: 0,
+            last_offset: Cell::new(Vec2::zero()),
+            last_size: Vec2::zero(),
+            dragging: false,
+        }
+    }
+
+    /// Creates a new split view with the panes side by side.
+    pub fn horizontal<A, B>(first: A, second: B) -> Self
+        where A: View + 'static,
+              B: View + 'static
+    {
+        SplitView::new(Orientation::Horizontal, first, second)
+    }
+
+    /// Creates a new split view with a pane above the other.
+    pub fn vertical<A, B>(first: A, second: B) -> Self
+        where A: View + 'static,
+              B: View + 'static
+    {
+        SplitView::new(Orientation::Vertical, first, second)
+    }
+
+    /// Sets the part of the space given to the first pane.
+    ///
+    /// `ratio` is clamped between 0 and 1.
+    /// Minimum and maximum pane sizes take precedence.
+    pub fn set_ratio(&mut self, ratio: f64) {
+        self.ratio = ratio.max(0.0).min(1.0);
+    }
+
+    /// Sets the part of the space given to the first pane.
+    ///
+    /// Chainable variant.
+    pub fn ratio(self, ratio: f64) -> Self {
+        self.with(|s| s.set_ratio(ratio))
+    }
+
+    /// Returns the part of the space given to the first pane.
+    pub fn get_ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Sets the minimum size of a pane along the split axis.
+    pub fn set_min_size(&mut self, pane: Pane, size: usize) {
+        self.children[pane.index()].min_size = size;
+    }
+
+    /// Sets the minimum size of a pane along the split axis.
+    ///
+    /// Chainable variant.
+    pub fn min_size(self, pane: Pane, size: usize) -> Self {
+        self.with(|s| s.set_min_size(pane, size))
+    }
+
+    /// Sets the maximum size of a pane along the split axis.
+    ///
+    /// `None` means no limit (the default).
+    pub fn set_max_size(&mut self, pane: Pane, size: Option<usize>) {
+        self.children[pane.index()].max_size = size;
+    }
+
+    /// Sets the maximum size of a pane along the split axis.
+    ///
+    /// Chainable variant.
+    pub fn max_size(self, pane: Pane, size: usize) -> Self {
+        self.with(|s| s.set_max_size(pane, Some(size)))
+    }
+
+    /// Collapses or restores a pane.
+    ///
+    /// A collapsed pane is hidden, and the other one takes all the space.
+    /// The ratio is kept, and used again when the pane is restored.
+    pub fn set_collapsed(&mut self, pane: Pane, collapsed: bool) {
+        self.children[pane.index()].collapsed = collapsed;
+        if collapsed {
+            // Only one pane can be collapsed at a time.
+            let other = 1 - pane.index();
+            self.children[other].collapsed = false;
+            if self.focus == pane.index() {
+                self.focus = other;
+            }
+        }
+    }
+
+    /// Returns `true` if the given pane is collapsed.
+    pub fn is_collapsed(&self, pane: Pane) -> bool {
+        self.children[pane.index()].collapsed
+    }
+
+    /// Returns a reference to the view in the given pane.
+    pub fn get_pane(&self, pane: Pane) -> &View {
+        &*self.children[pane.index()].view
+    }
+
+    /// Returns a mutable reference to the view in the given pane.
+    pub fn get_pane_mut(&mut self, pane: Pane) -> &mut View {
+        &mut *self.children[pane.index()].view
+    }
+
+    /// Returns the pane currently in focus.
+    pub fn focused_pane(&self) -> Pane {
+        if self.focus == 0 {
+            Pane::First
+        } else {
+            Pane::Second
+        }
+    }
+
+    // Computes the size of the first pane, given the space available.
+    fn split(&self, available: usize) -> usize {
+        if self.children[0].collapsed {
+            return 0;
+        }
+        if self.children[1].collapsed {
+            return available;
+        }
+
+        let first = (self.ratio * available as f64).round() as usize;
+        let first = self.children[0].clamp(first);
+        let second = available.saturating_sub(first);
+        let second = self.children[1].clamp(second);
+        // The first pane's minimum size wins over the second pane's.
+        let first = available.saturating_sub(second);
+        min(self.children[0].clamp(first), available)
+    }
+
+    // Moves the divider so the first pane has the given size.
+    fn move_divider(&mut self, position: usize) {
+        if self.available == 0 {
+            return;
+        }
+        let position = min(position, self.available);
+        self.ratio = position as f64 / self.available as f64;
+        self.position = self.split(self.available);
+    }
+
+    fn visible(&self, i: usize) -> bool {
+        !self.children[i].collapsed
+    }
+
+    fn size_of(&self, i: usize) -> usize {
+        if i == 0 {
+            self.position
+        } else {
+            self.available - self.position
+        }
+    }
+
+    fn child_offset(&self, i: usize) -> usize {
+        // Without the first pane, there is no divider either.
+        if i == 0 || !self.visible(0) {
+            0
+        } else {
+            self.position + 1
+        }
+    }
+
+    fn move_focus(&mut self, to: usize) -> EventResult {
+        if to == self.focus || !self.visible(to) {
+            return EventResult::Ignored;
+        }
+
+        let source = if to > self.focus {
+            Direction::front()
+        } else {
+            Direction::back()
+        };
+        if self.children[to].view.take_focus(source) {
+            self.change_focus(to)
+        } else {
+            EventResult::Ignored
+        }
+    }
+
+    // Position of the mouse along the split axis, relative to this view.
+    fn mouse_position(&self, position: Vec2) -> Option<usize> {
+        position.checked_sub(self.last_offset.get())
+            .map(|p| self.orientation.get(&p))
+    }
+
+    fn on_mouse_event(&mut self, position: Vec2, event: MouseEvent)
+                      -> Option<EventResult> {
+        let pos = match self.mouse_position(position) {
+            Some(pos) => pos,
+            None => return None,
+        };
+
+        match event {
+            MouseEvent::Press(MouseButton::Left) if pos == self.position &&
+                                                    self.visible(0) &&
+                                                    self.visible(1) => {
+                self.dragging = true;
+            }
+            MouseEvent::Hold(MouseButton::Left) if self.dragging => {
+                self.move_divider(pos);
+            }
+            MouseEvent::Release(MouseButton::Left) if self.dragging => {
+                self.dragging = false;
+            }
+            _ if event.is_positional() => {
+                return self.route_mouse_event(position, event)
+            }
+            _ => return None,
+        }
+
+        Some(EventResult::Consumed(None))
+    }
+}
+
+impl FocusContainer for SplitView {
+    type Child = usize;
+
+    fn last_offset(&self) -> Vec2 {
+        self.last_offset.get()
+    }
+
+    fn child_at(&self, position: Vec2) -> Option<usize> {
+        if !(position + (1, 1)).fits_in(self.last_size) {
+            return None;
+        }
+
+        let pos = self.orientation.get(&position);
+        (0..2).find(|&i| {
+            let offset = self.child_offset(i);
+            self.visible(i) && pos >= offset && pos < offset + self.size_of(i)
+        })
+    }
+
+    fn focused_child(&self) -> usize {
+        self.focus
+    }
+
+    fn set_focused_child(&mut self, i: usize) {
+        self.focus = i;
+    }
+
+    fn child_take_focus(&mut self, i: usize, source: Direction) -> bool {
+        self.visible(i) && self.children[i].view.take_focus(source)
+    }
+
+    fn child_on_event(&mut self, i: usize, event: Event) -> EventResult {
+        self.children[i].view.on_event(event)
+    }
+}
+
+impl View for SplitView {
+    fn draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
+
+        for i in 0..2 {
+            if !self.visible(i) {
+                continue;
+            }
+            let offset = self.orientation.make_vec(self.child_offset(i), 0);
+            let size = printer.size
+                .with_axis(self.orientation, self.size_of(i));
+            let printer = printer.sub_printer(offset, size, i == self.focus);
+            self.children[i].view.draw(&printer);
+        }
+
+        if self.visible(0) && self.visible(1) {
+            let length = self.orientation.swap().get(&printer.size);
+            let chars = printer.theme.borders.chars();
+            let mut buf = [0; 4];
+            match self.orientation {
+                Orientation::Horizontal => {
+                    let line = chars.vertical.encode_utf8(&mut buf);
+                    printer.print_vline((self.position, 0), length, line)
+                }
+                Orientation::Vertical => {
+                    let line = chars.horizontal.encode_utf8(&mut buf);
+                    printer.print_hline((0, self.position), length, line)
+                }
+            }
+        }
+    }
+
+    fn required_size(&mut self, req: Vec2) -> Vec2 {
+        let sizes: Vec<Vec2> = self.children
+            .iter_mut()
+            .filter(|child| !child.collapsed)
+            .map(|child| child.view.required_size(req))
+            .collect();
+        let divider = if sizes.len() > 1 { 1 } else { 0 };
+
+        self.orientation.stack(sizes.iter()) +
+        self.orientation.make_vec(divider, 0)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.last_size = size;
+        let divider = if self.visible(0) && self.visible(1) { 1 } else { 0 };
+        self.available = self.orientation.get(&size).saturating_sub(divider);
+        self.position = self.split(self.available);
+
+        for i in 0..2 {
+            if self.visible(i) {
+                let child_size = size.with_axis(self.orientation,
+                                                self.size_of(i));
+                self.children[i].view.layout(child_size);
+            }
+        }
+    }
+
+    fn take_focus(&mut self, source: Direction) -> bool {
+        let order = match source.relative(self.orientation) {
+            Some(::direction::Relative::Back) => [1, 0],
+            _ => [0, 1],
+        };
+
+        for &i in &order {
+            if self.visible(i) && self.children[i].view.take_focus(source) {
+                self.focus = i;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if let Event::Mouse { position, event } = event {
+            if let Some(result) = self.on_mouse_event(position, event) {
+                return result;
+            }
+        }
+
+        match self.children[self.focus].view.on_event(event.clone()) {
+            EventResult::Ignored => (),
+            res => return res,
+        }
+
+        let horizontal = self.orientation == Orientation::Horizontal;
+        match event {
+            Event::Key(Key::Tab) => self.move_focus(1),
+            Event::Shift(Key::Tab) => self.move_focus(0),
+            Event::Key(Key::Right) if horizontal => self.move_focus(1),
+            Event::Key(Key::Left) if horizontal => self.move_focus(0),
+            Event::Key(Key::Down) if !horizontal => self.move_focus(1),
+            Event::Key(Key::Up) if !horizontal => self.move_focus(0),
+            Event::Ctrl(Key::Right) if horizontal => {
+                let position = self.position + 1;
+                self.move_divider(position);
+                EventResult::Consumed(None)
+            }
+            Event::Ctrl(Key::Down) if !horizontal => {
+                let position = self.position + 1;
+                self.move_divider(position);
+                EventResult::Consumed(None)
+            }
+            Event::Ctrl(Key::Left) if horizontal => {
+                let position = self.position.saturating_sub(1);
+                self.move_divider(position);
+                EventResult::Consumed(None)
+            }
+            Event::Ctrl(Key::Up) if !horizontal => {
+                let position = self.position.saturating_sub(1);
+                self.move_divider(position);
+                EventResult::Consumed(None)
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn call_on_any<'a>(&mut self, selector: &Selector,
+                       mut callback: Box<FnMut(&mut Any) + 'a>) {
+        if let Selector::Path(path) = *selector {
+            match path.split_first() {
+                None => callback(self),
+                Some((i, rest)) => {
+                    if let Some(child) = self.children.get_mut(i) {
+                        let selector = Selector::Path(&rest);
+                        child.view.call_on_any(&selector, callback);
+                    }
+                }
+            }
+            return;
+        }
+
+        for child in &mut self.children {
+            child.view.call_on_any(selector, Box::new(|any| callback(any)));
+        }
+    }
+
+    fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        if let Selector::Path(path) = *selector {
+            let (i, rest) = match path.split_first() {
+                Some(turn) => turn,
+                None if self.take_focus(Direction::none()) => return Ok(()),
+                None => return Err(()),
+            };
+            if i >= 2 || !self.visible(i) ||
+               self.children[i]
+                   .view
+                   .focus_view(&Selector::Path(&rest))
+                   .is_err() {
+                return Err(());
+            }
+            self.focus = i;
+            return Ok(());
+        }
+
+        for i in 0..2 {
+            if self.visible(i) &&
+               self.children[i].view.focus_view(selector).is_ok() {
+                self.focus = i;
+                return Ok(());
+            }
+        }
+
+        Err(())
+    }
+
+    fn focused_id(&self) -> Option<String> {
+        self.children[self.focus].view.focused_id()
+    }
+
+    fn focused_path(&self) -> ViewPath {
+        let mut path = self.children[self.focus].view.focused_path();
+        path.path.insert(0, self.focus);
+        path
+    }
+
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        path.split_first().and_then(|(i, rest)| {
+            self.children.get(i).and_then(|child| child.view.id_at(&rest))
+        })
+    }
+
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        let mut info = ViewInfo::new::<Self>(size);
+        for i in 0..2 {
+            if !self.visible(i) {
+                continue;
+            }
+            let offset = self.orientation.make_vec(self.child_offset(i), 0);
+            let child_size = size.with_axis(self.orientation, self.size_of(i));
+            let child = self.children[i].view.inspect(child_size);
+            info.add_child(offset, i == self.focus, child);
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use views::DummyView;
+
+    #[test]
+    fn test_split() {
+        let mut split = SplitView::horizontal(DummyView, DummyView);
+        assert_eq!(split.split(20), 10);
+
+        split.set_ratio(0.25);
+        assert_eq!(split.split(20), 5);
+
+        split.set_min_size(Pane::First, 8);
+        assert_eq!(split.split(20), 8);
+
+        split.set_ratio(1.0);
+        split.set_max_size(Pane::Second, Some(4));
+        split.set_min_size(Pane::Second, 3);
+        assert_eq!(split.split(20), 17);
+
+        split.set_collapsed(Pane::First, true);
+        assert_eq!(split.split(20), 0);
+    }
+
+    #[test]
+    fn test_collapsed_layout() {
+        let mut split = SplitView::horizontal(DummyView, DummyView);
+        let size = Vec2::new(21, 3);
+        split.layout(size);
+        let info = split.inspect(size);
+        assert_eq!(info.children[1].offset, Vec2::new(11, 0));
+        assert_eq!(info.children[1].size, Vec2::new(10, 3));
+
+        // The remaining pane takes the whole view.
+        split.set_collapsed(Pane::First, true);
+        split.layout(size);
+        let info = split.inspect(size);
+        assert_eq!(info.children.len(), 1);
+        assert_eq!(info.children[0].offset, Vec2::zero());
+        assert_eq!(info.children[0].size, size);
+
+        split.set_collapsed(Pane::Second, true);
+        split.layout(size);
+        let info = split.inspect(size);
+        assert_eq!(info.children.len(), 1);
+        assert_eq!(info.children[0].offset, Vec2::zero());
+        assert_eq!(info.children[0].size, size);
+    }
+}
@@ -2,18 +2,65 @@ use Printer;
 
 use ::With;
 use direction::Direction;
-use event::{Event, EventResult};
+use event::{Event, EventResult, Key, MouseButton, MouseEvent};
 use std::any::Any;
+use std::cell::Cell;
+use std::mem;
 use theme::ColorStyle;
 use vec::Vec2;
-use view::{Offset, Position, Selector, View};
+use view::{Offset, Position, Selector, View, ViewInfo, ViewPath};
 use views::{Layer, ShadowView};
 
 /// Simple stack of views.
 /// Only the top-most view is active and can receive input.
+///
+/// # Window mode
+///
+/// When [window mode] is enabled, floating layers behave like windows:
+///
+/// * Clicking on a layer brings it to the front.
+/// * Dragging the top row of a layer moves it, and dragging its
+///   bottom-right corner resizes it.
+/// * `<F6>` and `<Shift-F6>` cycle through the windows.
+/// * `<Ctrl-F5>` starts moving the front window with the arrow keys,
+///   or resizing it with `<Shift>` and the arrow keys.
+///   `<Enter>` or `<Esc>` stops.
+/// * `<Alt-F3>` closes the front window.
+///
+/// Keys are only handled if the front window ignores them, and mouse
+/// events are only reported after `Cursive::set_mouse_capture`.
+///
+/// # Focus notifications
+///
+/// When the top layer changes, the previous one receives
+/// `Event::FocusLost` and the new one `Event::FocusGained`.
+/// New views get the focus once laid out, without a notification.
+///
+/// The results are returned with the next event, or by
+/// [`take_focus_events`]; `Cursive` calls it for the root stack.
+///
+/// [`take_focus_events`]: #method.take_focus_events
+/// [window mode]: #method.set_window_mode
 pub struct StackView {
     layers: Vec<Child>,
     last_size: Vec2,
+
+    window_mode: bool,
+    // Where we were last drawn, to locate clicks on the windows.
+    last_offset: Cell<Vec2>,
+    drag: Option<Drag>,
+    // Moving the front window with the keyboard?
+    moving: bool,
+    // Results of focus notifications, waiting for `take_focus_events`.
+    focus_events: EventResult,
+}
+
+// Current mouse action on the front window.
+#[derive(Clone, Copy)]
+enum Drag {
+    // Position of the mouse relative to the window's top-left corner.
+    Move(Vec2),
+    Resize,
 }
 
 enum Placement {
@@ -42,6 +89,11 @@ struct Child {
     size: Vec2,
     placement: Placement,
 
+    // Space left by the shadow above and left of the view.
+    padding: Vec2,
+    // Size set by the user, instead of the required size.
+    fixed_size: Option<Vec2>,
+
     // We cannot call `take_focus` until we've called `layout()`
     // So we want to call `take_focus` right after the first call
     // to `layout`; this flag remembers when we've done that.
@@ -56,6 +108,11 @@ impl StackView {
         StackView {
             layers: Vec::new(),
             last_size: Vec2::zero(),
+            window_mode: false,
+            last_offset: Cell::new(Vec2::zero()),
+            drag: None,
+            moving: false,
+            focus_events: EventResult::Ignored,
         }
     }
 
@@ -65,10 +122,13 @@ impl StackView {
     pub fn add_fullscreen_layer<T>(&mut self, view: T)
         where T: 'static + View
     {
+        self.notify_top(Event::FocusLost);
         self.layers.push(Child {
             view: Box::new(Layer::new(view)),
             size: Vec2::zero(),
             placement: Placement::Fullscreen,
+            padding: Vec2::zero(),
+            fixed_size: None,
             virgin: true,
         });
     }
@@ -102,13 +162,18 @@ impl StackView {
     pub fn add_layer_at<T>(&mut self, position: Position, view: T)
         where T: 'static + View
     {
+        // Skip padding for absolute/parent-placed views
+        let padding = Vec2::new((position.x == Offset::Center) as usize,
+                                (position.y == Offset::Center) as usize);
+        self.notify_top(Event::FocusLost);
         self.layers.push(Child {
-            // Skip padding for absolute/parent-placed views
             view: Box::new(ShadowView::new(Layer::new(view))
-                .top_padding(position.y == Offset::Center)
-                .left_padding(position.x == Offset::Center)),
+                .top_padding(padding.y == 1)
+                .left_padding(padding.x == 1)),
             size: Vec2::new(0, 0),
             placement: Placement::Floating(position),
+            padding: padding,
+            fixed_size: None,
             virgin: true,
         });
     }
@@ -124,28 +189,411 @@ impl StackView {
 
     /// Remove the top-most layer.
     pub fn pop_layer(&mut self) {
+        self.notify_top(Event::FocusLost);
         self.layers.pop();
+        self.notify_top(Event::FocusGained);
+        self.stop_moving();
+    }
+
+    /// Removes the layer at the given index.
+    ///
+    /// Index 0 is the bottom layer.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= self.len()`.
+    pub fn remove_layer(&mut self, index: usize) {
+        if index + 1 == self.layers.len() {
+            self.pop_layer();
+            return;
+        }
+        self.layers.remove(index);
+        self.stop_moving();
+    }
+
+    /// Moves a layer to a new index in the stack.
+    ///
+    /// Layers between `from` and `to` are shifted to make room.
+    ///
+    /// # Panics
+    ///
+    /// If `from` or `to` is out of bounds.
+    pub fn move_layer(&mut self, from: usize, to: usize) {
+        assert!(to < self.layers.len());
+        let top = self.top();
+        let changes_top = from != to && (from == top || to == top);
+        if changes_top {
+            self.notify_top(Event::FocusLost);
+        }
+        let layer = self.layers.remove(from);
+        self.layers.insert(to, layer);
+        if changes_top {
+            self.notify_top(Event::FocusGained);
+        }
+        self.stop_moving();
+    }
+
+    /// Moves the layer at the given index to the top of the stack.
+    ///
+    /// It will then receive input.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= self.len()`.
+    pub fn move_to_front(&mut self, index: usize) {
+        let top = self.top();
+        self.move_layer(index, top);
+    }
+
+    /// Moves the layer at the given index to the bottom of the stack.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= self.len()`.
+    pub fn move_to_back(&mut self, index: usize) {
+        self.move_layer(index, 0);
+    }
+
+    /// Replaces the view of a layer, keeping its position.
+    ///
+    /// If it is the top layer, the old view receives `Event::FocusLost`.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= self.len()`.
+    pub fn replace_layer<T>(&mut self, index: usize, view: T)
+        where T: 'static + View
+    {
+        if index == self.top() {
+            self.notify_top(Event::FocusLost);
+        }
+        let layer = &mut self.layers[index];
+        layer.view = match layer.placement {
+            Placement::Fullscreen => Box::new(Layer::new(view)),
+            Placement::Floating(_) => {
+                Box::new(ShadowView::new(Layer::new(view))
+                    .top_padding(layer.padding.y == 1)
+                    .left_padding(layer.padding.x == 1))
+            }
+        };
+        layer.virgin = true;
+    }
+
+    /// Returns the index of the layer containing the view with the given id.
+    ///
+    /// If several layers match, returns the top-most one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive::views::{Dialog, StackView, TextView};
+    /// # use cursive::traits::*;
+    /// let mut stack = StackView::new()
+    ///     .layer(Dialog::around(TextView::new("Downloading...")
+    ///                               .with_id("download")))
+    ///     .layer(Dialog::info("Welcome!"));
+    ///
+    /// // The download finished: close its dialog, even if it's not on top.
+    /// if let Some(i) = stack.find_layer_from_id("download") {
+    ///     stack.remove_layer(i);
+    /// }
+    /// assert_eq!(stack.len(), 1);
+    /// ```
+    pub fn find_layer_from_id(&mut self, id: &str) -> Option<usize> {
+        let selector = Selector::Id(id);
+        (0..self.layers.len()).rev().find(|&i| {
+            let mut found = false;
+            self.layers[i]
+                .view
+                .call_on_any(&selector, Box::new(|_| found = true));
+            found
+        })
+    }
+
+    /// Moves a floating layer to a new position.
+    ///
+    /// Does nothing for fullscreen layers.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= self.len()`.
+    pub fn reposition_layer(&mut self, index: usize, position: Position) {
+        let layer = &mut self.layers[index];
+        if let Placement::Floating(_) = layer.placement {
+            layer.placement = Placement::Floating(position);
+        }
+    }
+
+    /// Sets the size of a floating layer, shadow included.
+    ///
+    /// `None` goes back to the size required by the view.
+    /// Does nothing for fullscreen layers.
+    ///
+    /// # Panics
+    ///
+    /// If `index >= self.len()`.
+    pub fn resize_layer(&mut self, index: usize, size: Option<Vec2>) {
+        let layer = &mut self.layers[index];
+        if let Placement::Floating(_) = layer.placement {
+            layer.fixed_size = size;
+        }
+    }
+
+    /// Returns the number of layers.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns `true` if there is no layer.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Enables or disables window mode.
+    ///
+    /// In window mode, floating layers can be moved, resized,
+    /// reordered and closed by the user.
+    pub fn set_window_mode(&mut self, enabled: bool) {
+        self.window_mode = enabled;
+        self.drag = None;
+        self.moving = false;
+    }
+
+    /// Enables or disables window mode.
+    ///
+    /// Chainable variant.
+    pub fn window_mode(self, enabled: bool) -> Self {
+        self.with(|s| s.set_window_mode(enabled))
+    }
+
+    /// Returns `true` if window mode is enabled.
+    pub fn is_window_mode(&self) -> bool {
+        self.window_mode
+    }
+
+    /// Brings the bottom-most floating layer to the front.
+    ///
+    /// Does nothing if there are less than two floating layers.
+    pub fn focus_next_window(&mut self) {
+        if let Some(i) = self.first_window() {
+            if i + 1 < self.layers.len() && self.is_window(self.top()) {
+                self.move_to_front(i);
+            }
+        }
+    }
+
+    /// Sends the top layer behind the other floating layers.
+    ///
+    /// Does nothing if the top layer is not floating.
+    pub fn focus_previous_window(&mut self) {
+        if let Some(i) = self.first_window() {
+            let top = self.top();
+            if i < top && self.is_window(top) {
+                self.move_layer(top, i);
+            }
+        }
     }
 
     /// Computes the offset of the current top view.
     pub fn offset(&self) -> Vec2 {
-        let mut previous = Vec2::zero();
-        for layer in &self.layers {
-            let offset = layer.placement
-                .compute_offset(layer.size, self.last_size, previous);
-            previous = offset;
-        }
-        previous
+        self.layer_offsets().last().cloned().unwrap_or_else(Vec2::zero)
     }
 
     /// Returns the size for each layer in this view.
     pub fn layer_sizes(&self) -> Vec<Vec2> {
         self.layers.iter().map(|layer| layer.size).collect()
     }
+
+    /// Returns the offset of each layer in this view.
+    pub fn layer_offsets(&self) -> Vec<Vec2> {
+        let mut previous = Vec2::zero();
+        self.layers
+            .iter()
+            .map(|layer| {
+                previous = layer.placement
+                    .compute_offset(layer.size, self.last_size, previous);
+                previous
+            })
+            .collect()
+    }
+
+    /// Returns the results of the focus notifications sent so far.
+    ///
+    /// Callbacks, like the ones from `EditView::on_blur`, should be run
+    /// by the caller. `Cursive` does it for the root stack.
+    pub fn take_focus_events(&mut self) -> EventResult {
+        mem::replace(&mut self.focus_events, EventResult::Ignored)
+    }
+
+    fn top(&self) -> usize {
+        self.layers.len().saturating_sub(1)
+    }
+
+    // Sends a focus notification to the top layer.
+    fn notify_top(&mut self, event: Event) {
+        let result = match self.layers.last_mut() {
+            // Layers that were never laid out didn't take the focus yet.
+            Some(layer) if !layer.virgin => layer.view.on_event(event),
+            _ => return,
+        };
+        let events = self.take_focus_events();
+        self.focus_events = events.and(result);
+    }
+
+    fn is_window(&self, index: usize) -> bool {
+        match self.layers.get(index) {
+            Some(&Child { placement: Placement::Floating(_), .. }) => true,
+            _ => false,
+        }
+    }
+
+    fn first_window(&self) -> Option<usize> {
+        (0..self.layers.len()).find(|&i| self.is_window(i))
+    }
+
+    fn stop_moving(&mut self) {
+        self.drag = None;
+        self.moving = false;
+    }
+
+    // Moves the top layer by the given amount.
+    fn move_top(&mut self, dx: isize, dy: isize) {
+        let top = self.top();
+        let offset = self.offset().signed() + (dx, dy);
+        let offset = Vec2::new(offset.x.max(0) as usize,
+                               offset.y.max(0) as usize);
+        self.reposition_layer(top, Position::absolute(offset));
+    }
+
+    // Resizes the top layer by the given amount.
+    fn resize_top(&mut self, dx: isize, dy: isize) {
+        let top = self.top();
+        let size = self.layers[top].size.signed() + (dx, dy);
+        self.resize_top_to(Vec2::new(size.x.max(0) as usize,
+                                     size.y.max(0) as usize));
+    }
+
+    fn resize_top_to(&mut self, size: Vec2) {
+        let top = self.top();
+        // Keep room for the borders and the shadow.
+        let size = Vec2::max(size, self.layers[top].padding + (4, 3));
+        self.resize_layer(top, Some(size));
+    }
+
+    // Handles keys while moving the top window with the keyboard.
+    fn on_moving_event(&mut self, event: &Event) -> EventResult {
+        match *event {
+            Event::Key(Key::Left) => self.move_top(-1, 0),
+            Event::Key(Key::Right) => self.move_top(1, 0),
+            Event::Key(Key::Up) => self.move_top(0, -1),
+            Event::Key(Key::Down) => self.move_top(0, 1),
+            Event::Shift(Key::Left) => self.resize_top(-1, 0),
+            Event::Shift(Key::Right) => self.resize_top(1, 0),
+            Event::Shift(Key::Up) => self.resize_top(0, -1),
+            Event::Shift(Key::Down) => self.resize_top(0, 1),
+            Event::Key(Key::Enter) |
+            Event::Key(Key::Esc) => self.moving = false,
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
+
+    // Handles mouse events in window mode.
+    //
+    // Returns `None` if the event should go to the top layer.
+    fn on_window_mouse_event(&mut self, position: Vec2, event: MouseEvent)
+                             -> Option<EventResult> {
+        let position = match position.checked_sub(self.last_offset.get()) {
+            Some(position) => position,
+            None => return None,
+        };
+
+        match (self.drag, event) {
+            (Some(Drag::Move(grab)), MouseEvent::Hold(MouseButton::Left)) => {
+                let top = self.top();
+                let offset = position.saturating_sub(grab);
+                self.reposition_layer(top, Position::absolute(offset));
+            }
+            (Some(Drag::Resize), MouseEvent::Hold(MouseButton::Left)) => {
+                let offset = self.offset();
+                let size = position.saturating_sub(offset) + (2, 2);
+                self.resize_top_to(size);
+            }
+            (Some(_), MouseEvent::Release(MouseButton::Left)) => {
+                self.drag = None;
+            }
+            (None, MouseEvent::Press(_)) => {
+                return self.on_window_click(position, event);
+            }
+            _ => return None,
+        }
+
+        Some(EventResult::Consumed(None))
+    }
+
+    // A click raises the window below, or starts moving or resizing it.
+    fn on_window_click(&mut self, position: Vec2, event: MouseEvent)
+                       -> Option<EventResult> {
+        let offsets = self.layer_offsets();
+        // Only floating windows above the last fullscreen layer can be
+        // raised: clicks on the background are left to the top layer.
+        let clicked = (0..self.layers.len())
+            .rev()
+            .take_while(|&i| self.is_window(i))
+            .find(|&i| {
+                position.fits(offsets[i]) &&
+                (position - offsets[i] + (1, 1))
+                    .fits_in(self.layers[i].size)
+            });
+        let i = match clicked {
+            Some(i) => i,
+            None => return None,
+        };
+
+        if i != self.top() {
+            self.move_to_front(i);
+        }
+
+        if !self.is_window(self.top()) ||
+           event != MouseEvent::Press(MouseButton::Left) {
+            return None;
+        }
+
+        // Coordinates within the window itself, without the shadow.
+        let top = self.top();
+        let offset = offsets[i] + self.layers[top].padding;
+        let corner = offsets[i] + self.layers[top].size - (2, 2);
+        if position == corner {
+            self.drag = Some(Drag::Resize);
+        } else if position.y == offset.y && position.x >= offset.x {
+            self.drag = Some(Drag::Move(position - offsets[i]));
+        } else {
+            return None;
+        }
+
+        Some(EventResult::Consumed(None))
+    }
+
+    // Handles window management keys ignored by the top layer.
+    fn on_window_event(&mut self, event: &Event) -> EventResult {
+        match *event {
+            Event::Key(Key::F6) => self.focus_next_window(),
+            Event::Shift(Key::F6) => self.focus_previous_window(),
+            Event::Ctrl(Key::F5) if self.is_window(self.top()) => {
+                self.moving = true
+            }
+            Event::Alt(Key::F3) if self.is_window(self.top()) => {
+                self.pop_layer()
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
 }
 
 impl View for StackView {
     fn draw(&self, printer: &Printer) {
+        self.last_offset.set(printer.offset);
         let last = self.layers.len();
         let mut previous = Vec2::zero();
         printer.with_color(ColorStyle::Primary, |printer| {
@@ -163,10 +611,32 @@ impl View for StackView {
     }
 
     fn on_event(&mut self, event: Event) -> EventResult {
-        match self.layers.last_mut() {
-            None => EventResult::Ignored,
-            Some(v) => v.view.on_event(event),
+        if self.window_mode {
+            if self.moving {
+                return self.on_moving_event(&event);
+            }
+
+            if let Event::Mouse { position, event } = event {
+                if let Some(result) =
+                    self.on_window_mouse_event(position, event) {
+                    return result;
+                }
+            }
         }
+
+        let result = match self.layers.last_mut() {
+            None => EventResult::Ignored,
+            Some(v) => v.view.on_event(event.clone()),
+        };
+
+        let result = match result {
+            EventResult::Ignored if self.window_mode => {
+                self.on_window_event(&event)
+            }
+            result => result,
+        };
+        // Nested stacks are not drained by `Cursive`.
+        self.take_focus_events().and(result)
     }
 
     fn layout(&mut self, size: Vec2) {
@@ -177,7 +647,10 @@ impl View for StackView {
 
         for layer in &mut self.layers {
             // Give each guy what he asks for, within the budget constraints.
-            let size = Vec2::min(size, layer.view.required_size(size));
+            let size = match layer.fixed_size {
+                Some(fixed_size) => Vec2::min(size, fixed_size),
+                None => Vec2::min(size, layer.view.required_size(size)),
+            };
             layer.size = size;
             layer.view.layout(layer.size);
 
@@ -210,12 +683,38 @@ impl View for StackView {
 
     fn call_on_any<'a>(&mut self, selector: &Selector,
                     mut callback: Box<FnMut(&mut Any) + 'a>) {
+        if let Selector::Path(path) = *selector {
+            match path.split_first() {
+                None => callback(self),
+                Some((i, rest)) => {
+                    if let Some(layer) = self.layers.get_mut(i) {
+                        let selector = Selector::Path(&rest);
+                        layer.view.call_on_any(&selector, callback);
+                    }
+                }
+            }
+            return;
+        }
+
         for layer in &mut self.layers {
             layer.view.call_on_any(selector, Box::new(|any| callback(any)));
         }
     }
 
     fn focus_view(&mut self, selector: &Selector) -> Result<(), ()> {
+        if let Selector::Path(path) = *selector {
+            // Focusing a layer doesn't bring it to the front.
+            return match path.split_first() {
+                       Some((i, rest)) => match self.layers.get_mut(i) {
+                           Some(layer) => {
+                               layer.view.focus_view(&Selector::Path(&rest))
+                           }
+                           None => Err(()),
+                       },
+                       None => Err(()),
+                   };
+        }
+
         for layer in &mut self.layers {
             if layer.view.focus_view(selector).is_ok() {
                 return Ok(());
@@ -224,4 +723,220 @@ impl View for StackView {
 
         Err(())
     }
+
+    fn focused_id(&self) -> Option<String> {
+        self.layers.last().and_then(|layer| layer.view.focused_id())
+    }
+
+    fn focused_path(&self) -> ViewPath {
+        let mut path = ViewPath::new();
+        if let Some(layer) = self.layers.last() {
+            path = layer.view.focused_path();
+            path.path.insert(0, self.layers.len() - 1);
+        }
+        path
+    }
+
+    fn id_at(&self, path: &ViewPath) -> Option<String> {
+        path.split_first().and_then(|(i, rest)| {
+            self.layers.get(i).and_then(|layer| layer.view.id_at(&rest))
+        })
+    }
+
+    fn inspect(&self, size: Vec2) -> ViewInfo {
+        let mut info = ViewInfo::new::<Self>(size);
+        let last = self.layers.len();
+        let mut previous = Vec2::zero();
+        for (i, v) in self.layers.iter().enumerate() {
+            // Same placement as in `draw`.
+            let offset = v.placement.compute_offset(v.size, size, previous);
+            previous = offset;
+            info.add_child(offset, i + 1 == last, v.view.inspect(v.size));
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use view::{Finder, Identifiable};
+    use views::{EditView, LinearLayout, TextView};
+    use views::tests::FocusProbe;
+
+    fn layer_widths(stack: &StackView) -> Vec<usize> {
+        stack.layer_sizes().iter().map(|size| size.x).collect()
+    }
+
+    #[test]
+    fn test_window_order() {
+        let mut stack = StackView::new()
+            .fullscreen_layer(TextView::new("background"))
+            .layer(TextView::new("a"))
+            .layer(TextView::new("bb"))
+            .layer(TextView::new("ccc"));
+        stack.layout(Vec2::new(80, 24));
+        // Floating layers include the padding and the shadow.
+        assert_eq!(layer_widths(&stack), vec![10, 3, 4, 5]);
+
+        stack.focus_next_window();
+        stack.layout(Vec2::new(80, 24));
+        assert_eq!(layer_widths(&stack), vec![10, 4, 5, 3]);
+
+        stack.focus_previous_window();
+        stack.layout(Vec2::new(80, 24));
+        assert_eq!(layer_widths(&stack), vec![10, 3, 4, 5]);
+
+        stack.move_to_back(3);
+        stack.remove_layer(1);
+        stack.layout(Vec2::new(80, 24));
+        assert_eq!(layer_widths(&stack), vec![5, 3, 4]);
+    }
+
+    #[test]
+    fn test_window_click() {
+        let mut stack = StackView::new()
+            .window_mode(true)
+            .fullscreen_layer(TextView::new("background"))
+            .layer(TextView::new("aaaaaaaa"))
+            .layer(TextView::new("bb"));
+        stack.layout(Vec2::new(80, 24));
+        let click = |position: Vec2| {
+            Event::Mouse {
+                position: position,
+                event: MouseEvent::Press(MouseButton::Left),
+            }
+        };
+
+        // A click on the desktop keeps the windows above the background.
+        stack.on_event(click(Vec2::zero()));
+        stack.layout(Vec2::new(80, 24));
+        assert_eq!(layer_widths(&stack), vec![10, 10, 4]);
+
+        // A click on a window raises it.
+        let position = stack.layer_offsets()[1];
+        stack.on_event(click(position));
+        stack.layout(Vec2::new(80, 24));
+        assert_eq!(layer_widths(&stack), vec![10, 4, 10]);
+    }
+
+    #[test]
+    fn test_focus_events() {
+        let bottom = FocusProbe::new();
+        let top = FocusProbe::new();
+        let mut stack = StackView::new().layer(bottom.clone());
+
+        // Layers that were never shown don't have the focus to lose.
+        stack.add_layer(top.clone());
+        stack.pop_layer();
+        assert_eq!((bottom.lost(), bottom.gained(), top.lost()), (0, 0, 0));
+
+        // A dialog covering the view blurs it until it is closed.
+        stack.layout(Vec2::new(80, 24));
+        stack.add_layer(top.clone());
+        assert_eq!((bottom.lost(), bottom.gained()), (1, 0));
+
+        stack.layout(Vec2::new(80, 24));
+        stack.pop_layer();
+        assert_eq!(top.lost(), 1);
+        assert_eq!((bottom.lost(), bottom.gained()), (1, 1));
+    }
+
+    #[test]
+    fn test_replace_focus_events() {
+        let bottom = FocusProbe::new();
+        let top = FocusProbe::new();
+        let mut stack = StackView::new()
+            .layer(bottom.clone())
+            .layer(top.clone());
+        stack.layout(Vec2::new(80, 24));
+
+        stack.replace_layer(0, TextView::new("a"));
+        assert_eq!(bottom.lost(), 0);
+        stack.replace_layer(1, TextView::new("b"));
+        assert_eq!(top.lost(), 1);
+    }
+
+    #[test]
+    fn test_nested_focus_events() {
+        let probe = FocusProbe::new();
+        let mut stack = StackView::new()
+            .layer(probe.clone())
+            .layer(TextView::new("a"));
+        stack.layout(Vec2::new(80, 24));
+        stack.pop_layer();
+        assert_eq!(probe.gained(), 1);
+
+        // Without a `Cursive` root, the next event drains them.
+        match stack.on_event(Event::Refresh) {
+            EventResult::Consumed(_) => (),
+            EventResult::Ignored => panic!("focus events were not returned"),
+        }
+        match stack.take_focus_events() {
+            EventResult::Ignored => (),
+            EventResult::Consumed(_) => panic!("focus events were kept"),
+        }
+    }
+
+    #[test]
+    fn test_replace_layer() {
+        let mut stack = StackView::new()
+            .layer(TextView::new("a").with_id("a"))
+            .layer(TextView::new("b"));
+        assert_eq!(stack.find_layer_from_id("a"), Some(0));
+
+        stack.replace_layer(0, TextView::new("aaaa"));
+        stack.replace_layer(1, TextView::new("bb").with_id("a"));
+        stack.layout(Vec2::new(80, 24));
+        assert_eq!(layer_widths(&stack), vec![6, 4]);
+        assert_eq!(stack.find_layer_from_id("a"), Some(1));
+    }
+
+    #[test]
+    fn test_focused_path() {
+        let mut stack = StackView::new()
+            .layer(TextView::new("background"))
+            .layer(LinearLayout::vertical()
+                       .child(EditView::new().with_id("a"))
+                       .child(EditView::new().with_id("b")));
+        assert_eq!(stack.focused_path(), ViewPath::from(&[1, 0][..]));
+        assert_eq!(stack.focused_id(), Some("a".to_string()));
+
+        let path = ViewPath::from(&[1, 1][..]);
+        assert!(stack.focus_view(&Selector::Path(&path)).is_ok());
+        assert_eq!(stack.focused_path(), path);
+        assert_eq!(stack.id_at(&path), Some("b".to_string()));
+        assert_eq!(stack.call_on(&Selector::Path(&path),
+                                 |v: &mut EditView| v.set_content("b")),
+                   Some(()));
+
+        let missing = ViewPath::from(&[1, 2][..]);
+        assert!(stack.focus_view(&Selector::Path(&missing)).is_err());
+        assert_eq!(stack.id_at(&missing), None);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let mut stack = StackView::new()
+            .fullscreen_layer(TextView::new("background"))
+            .layer(LinearLayout::vertical()
+                       .child(EditView::new().with_id("a"))
+                       .child(TextView::new("bb")));
+        stack.layout(Vec2::new(80, 24));
+
+        let info = stack.inspect(Vec2::new(80, 24));
+        assert_eq!(info.name, "StackView");
+        assert!(!info.children[0].focused);
+        assert!(info.children[1].focused);
+
+        // ShadowView > Layer > LinearLayout
+        let shadow = &info.children[1];
+        let layout = &shadow.children[0].children[0];
+        assert_eq!(layout.name, "LinearLayout");
+        assert_eq!(layout.offset, Vec2::zero());
+        assert_eq!(layout.children[0].name, "EditView");
+        assert_eq!(layout.children[0].id, Some("a".to_string()));
+        assert_eq!(layout.children[1].offset, Vec2::new(0, 1));
+        assert!(info.to_string().contains("  EditView #a "));
+    }
 }
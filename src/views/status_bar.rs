@@ -0,0 +1,177 @@
+use Printer;
+use With;
+use event::{Event, EventResult};
+use theme::ColorStyle;
+use unicode_width::UnicodeWidthStr;
+use vec::Vec2;
+use view::View;
+
+/// Single row at the bottom of the screen, used to show status information.
+///
+/// It either shows three text segments (aligned left, center and right),
+/// or a custom view. It never takes the focus.
+///
+/// The status bar is owned by the root [`Cursive`] element, and hidden
+/// by default.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate cursive;
+/// # use cursive::Cursive;
+/// # fn main() {
+/// let mut siv = Cursive::new();
+///
+/// siv.set_statusbar_visible(true);
+/// siv.statusbar()
+///     .set_left("<Tab> next field")
+///     .set_right("INSERT");
+/// # }
+/// ```
+///
+/// [`Cursive`]: ../struct.Cursive.html
+pub struct StatusBar {
+    left: String,
+    center: String,
+    right: String,
+    view: Option<Box<View>>,
+}
+
+new_default!(StatusBar);
+
+impl StatusBar {
+    /// Creates a new, empty status bar.
+    pub fn new() -> Self {
+        StatusBar {
+            left: String::new(),
+            center: String::new(),
+            right: String::new(),
+            view: None,
+        }
+    }
+
+    /// Sets the left-aligned text.
+    pub fn set_left<S: Into<String>>(&mut self, text: S) -> &mut Self {
+        self.left = text.into();
+        self
+    }
+
+    /// Sets the centered text.
+    pub fn set_center<S: Into<String>>(&mut self, text: S) -> &mut Self {
+        self.center = text.into();
+        self
+    }
+
+    /// Sets the right-aligned text.
+    pub fn set_right<S: Into<String>>(&mut self, text: S) -> &mut Self {
+        self.right = text.into();
+        self
+    }
+
+    /// Sets the left-aligned text.
+    ///
+    /// Chainable variant.
+    pub fn left<S: Into<String>>(self, text: S) -> Self {
+        self.with(|s| {
+            s.set_left(text);
+        })
+    }
+
+    /// Sets the centered text.
+    ///
+    /// Chainable variant.
+    pub fn center<S: Into<String>>(self, text: S) -> Self {
+        self.with(|s| {
+            s.set_center(text);
+        })
+    }
+
+    /// Sets the right-aligned text.
+    ///
+    /// Chainable variant.
+    pub fn right<S: Into<String>>(self, text: S) -> Self {
+        self.with(|s| {
+            s.set_right(text);
+        })
+    }
+
+    /// Returns the left-aligned text.
+    pub fn get_left(&self) -> &str {
+        &self.left
+    }
+
+    /// Returns the centered text.
+    pub fn get_center(&self) -> &str {
+        &self.center
+    }
+
+    /// Returns the right-aligned text.
+    pub fn get_right(&self) -> &str {
+        &self.right
+    }
+
+    /// Clears all text segments.
+    pub fn clear(&mut self) {
+        self.left.clear();
+        self.center.clear();
+        self.right.clear();
+    }
+
+    /// Shows the given view instead of the text segments.
+    ///
+    /// The view is given a single row.
+    pub fn set_view<V: View + 'static>(&mut self, view: V) {
+        self.view = Some(Box::new(view));
+    }
+
+    /// Removes the custom view, if any, and returns it.
+    pub fn take_view(&mut self) -> Option<Box<View>> {
+        self.view.take()
+    }
+
+    /// Returns a mutable reference to the custom view, if any.
+    pub fn get_view_mut(&mut self) -> Option<&mut Box<View>> {
+        self.view.as_mut()
+    }
+}
+
+impl View for StatusBar {
+    fn draw(&self, printer: &Printer) {
+        printer.with_color(ColorStyle::Primary, |printer| {
+            printer.print_hline((0, 0), printer.size.x, " ");
+        });
+
+        if let Some(ref view) = self.view {
+            view.draw(printer);
+            return;
+        }
+
+        // Right and center texts may be hidden by the left one
+        // if the screen is too small.
+        let width = printer.size.x;
+        let right = self.right.width();
+        printer.print((width.saturating_sub(right + 1), 0), &self.right);
+
+        let center = self.center.width();
+        printer.print((width.saturating_sub(center) / 2, 0), &self.center);
+
+        printer.print((1, 0), &self.left);
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        Vec2::new(constraint.x, 1)
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        if let Some(ref mut view) = self.view {
+            view.layout(size);
+        }
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match self.view {
+            Some(ref mut view) => view.on_event(event),
+            None => EventResult::Ignored,
+        }
+    }
+}
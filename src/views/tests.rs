@@ -0,0 +1,96 @@
+//! Helpers shared by the view tests.
+
+use Printer;
+use direction::Direction;
+use event::{Event, EventResult, Key};
+use std::cell::Cell;
+use std::rc::Rc;
+use vec::Vec2;
+use view::View;
+use views::{Dialog, EditView, LinearLayout, ListView};
+
+/// Focusable view counting the focus notifications it receives.
+///
+/// Clones share the same counters, so a clone can be given to a container
+/// and the original kept to check them.
+#[derive(Clone)]
+pub struct FocusProbe {
+    lost: Rc<Cell<usize>>,
+    gained: Rc<Cell<usize>>,
+}
+
+new_default!(FocusProbe);
+
+impl FocusProbe {
+    /// Creates a probe that didn't receive anything yet.
+    pub fn new() -> Self {
+        FocusProbe {
+            lost: Rc::new(Cell::new(0)),
+            gained: Rc::new(Cell::new(0)),
+        }
+    }
+
+    /// Number of `Event::FocusLost` received.
+    pub fn lost(&self) -> usize {
+        self.lost.get()
+    }
+
+    /// Number of `Event::FocusGained` received.
+    pub fn gained(&self) -> usize {
+        self.gained.get()
+    }
+}
+
+impl View for FocusProbe {
+    fn draw(&self, _: &Printer) {}
+
+    fn required_size(&mut self, _: Vec2) -> Vec2 {
+        Vec2::new(1, 1)
+    }
+
+    fn take_focus(&mut self, _: Direction) -> bool {
+        true
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        match event {
+            Event::FocusLost => self.lost.set(self.lost.get() + 1),
+            Event::FocusGained => self.gained.set(self.gained.get() + 1),
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
+}
+
+// Tabs away from `probe`, the first view in `container`, and back.
+fn check_tab_blur<V: View>(mut container: V, probe: &FocusProbe) {
+    container.layout(Vec2::new(20, 5));
+    assert!(container.take_focus(Direction::front()));
+
+    assert!(container.on_event(Event::Key(Key::Tab)).is_consumed());
+    assert_eq!((probe.lost(), probe.gained()), (1, 0));
+
+    assert!(container.on_event(Event::Shift(Key::Tab)).is_consumed());
+    assert_eq!((probe.lost(), probe.gained()), (1, 1));
+}
+
+#[test]
+fn test_tab_blur() {
+    let probe = FocusProbe::new();
+    check_tab_blur(LinearLayout::vertical()
+                       .child(probe.clone())
+                       .child(EditView::new()),
+                   &probe);
+
+    // Delimiters are skipped.
+    let probe = FocusProbe::new();
+    check_tab_blur(ListView::new()
+                       .child("a", probe.clone())
+                       .delimiter()
+                       .child("b", EditView::new()),
+                   &probe);
+
+    let probe = FocusProbe::new();
+    check_tab_blur(Dialog::around(probe.clone()).button("Ok", |_| ()),
+                   &probe);
+}
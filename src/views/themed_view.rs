@@ -0,0 +1,200 @@
+use Printer;
+use With;
+use std::cell::{Ref, RefCell};
+use theme::{BorderStyle, Color, ColorStyle, Theme};
+use view::{View, ViewWrapper};
+
+/// Wrapper view that changes the theme for its whole subtree.
+///
+/// Views inside it are drawn with the current theme, after applying the
+/// given overrides. Palette entries are set by name, like in theme files,
+/// so they can also define colors for [`ColorStyle::Named`].
+///
+/// The area of the wrapper is first cleared with the new `view` color.
+///
+/// Shadows are drawn by the `StackView` around each layer: overriding
+/// them only affects layers of stack views inside the wrapper.
+///
+/// # Examples
+///
+/// ```no_run
+/// # extern crate cursive;
+/// # use cursive::Cursive;
+/// # use cursive::theme::{BaseColor, Color};
+/// # use cursive::views::{Dialog, ThemedView};
+/// # fn main() {
+/// let mut siv = Cursive::new();
+///
+/// let dialog = Dialog::text("Delete everything?")
+///     .button("Delete", |s| s.quit())
+///     .dismiss_button("Cancel");
+/// siv.add_layer(ThemedView::new(dialog)
+///                   .color("view", Color::Dark(BaseColor::Red))
+///                   .color("primary", Color::Light(BaseColor::White)));
+/// # }
+/// ```
+///
+/// [`ColorStyle::Named`]: ../theme/enum.ColorStyle.html#variant.Named
+pub struct ThemedView<V: View> {
+    view: V,
+    colors: Vec<(String, Color)>,
+    borders: Option<BorderStyle>,
+    shadow: Option<bool>,
+    // The last parent theme, and the same theme with our overrides.
+    cache: RefCell<Option<(Theme, Theme)>>,
+}
+
+impl<V: View> ThemedView<V> {
+    /// Wraps `view`, without any override yet.
+    pub fn new(view: V) -> Self {
+        ThemedView {
+            view: view,
+            colors: Vec::new(),
+            borders: None,
+            shadow: None,
+            cache: RefCell::new(None),
+        }
+    }
+
+    /// Overrides the palette entry with the given name.
+    pub fn set_color(&mut self, name: &str, color: Color) {
+        self.colors.retain(|&(ref key, _)| key != name);
+        self.colors.push((name.to_string(), color));
+        self.invalidate();
+    }
+
+    /// Overrides the palette entry with the given name.
+    ///
+    /// Chainable variant.
+    pub fn color(self, name: &str, color: Color) -> Self {
+        self.with(|s| s.set_color(name, color))
+    }
+
+    /// Overrides the border style.
+    pub fn set_border_style(&mut self, style: BorderStyle) {
+        self.borders = Some(style);
+        self.invalidate();
+    }
+
+    /// Overrides the border style.
+    ///
+    /// Chainable variant.
+    pub fn border_style(self, style: BorderStyle) -> Self {
+        self.with(|s| s.set_border_style(style))
+    }
+
+    /// Overrides whether layers should have shadows.
+    pub fn set_shadow(&mut self, shadow: bool) {
+        self.shadow = Some(shadow);
+        self.invalidate();
+    }
+
+    /// Overrides whether layers should have shadows.
+    ///
+    /// Chainable variant.
+    pub fn shadow(self, shadow: bool) -> Self {
+        self.with(|s| s.set_shadow(shadow))
+    }
+
+    /// Removes all overrides.
+    pub fn clear_overrides(&mut self) {
+        self.colors.clear();
+        self.borders = None;
+        self.shadow = None;
+        self.invalidate();
+    }
+
+    /// Returns a reference to the wrapped view.
+    pub fn get_view(&self) -> &V {
+        &self.view
+    }
+
+    /// Returns a mutable reference to the wrapped view.
+    pub fn get_view_mut(&mut self) -> &mut V {
+        &mut self.view
+    }
+
+    fn invalidate(&mut self) {
+        *self.cache.get_mut() = None;
+    }
+
+    // Returns the theme to draw the wrapped view with.
+    //
+    // It is only rebuilt when the parent theme or an override changed.
+    fn theme<'a>(&'a self, parent: &Theme) -> Ref<'a, Theme> {
+        let outdated = match *self.cache.borrow() {
+            Some((ref last, _)) => last != parent,
+            None => true,
+        };
+        if outdated {
+            let mut theme = parent.clone();
+            self.apply(&mut theme);
+            *self.cache.borrow_mut() = Some((parent.clone(), theme));
+        }
+        Ref::map(self.cache.borrow(), |cache| &cache.as_ref().unwrap().1)
+    }
+
+    fn apply(&self, theme: &mut Theme) {
+        for &(ref name, color) in &self.colors {
+            theme.colors.set(name, color);
+        }
+        if let Some(borders) = self.borders {
+            theme.borders = borders;
+        }
+        if let Some(shadow) = self.shadow {
+            theme.shadow = shadow;
+        }
+    }
+}
+
+impl<V: View> ViewWrapper for ThemedView<V> {
+    wrap_impl!(self.view: V);
+
+    fn wrap_draw(&self, printer: &Printer) {
+        let theme = self.theme(printer.theme);
+        let printer = printer.with_theme(&theme);
+
+        printer.with_color(ColorStyle::Primary, |printer| {
+            for y in 0..printer.size.y {
+                printer.print_hline((0, y), printer.size.x, " ");
+            }
+        });
+        self.view.draw(&printer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use theme::{self, BaseColor};
+    use views::DummyView;
+
+    #[test]
+    fn test_theme() {
+        let red = Color::Dark(BaseColor::Red);
+        let mut view = ThemedView::new(DummyView)
+            .color("view", red)
+            .color("warning", red)
+            .border_style(BorderStyle::Double);
+        let parent = theme::load_default();
+
+        {
+            let theme = view.theme(&parent);
+            assert_eq!(theme.colors.get("view"), Some(red));
+            assert_eq!(theme.colors.get("warning"), Some(red));
+            assert_eq!(theme.borders, BorderStyle::Double);
+            assert_eq!(theme.shadow, parent.shadow);
+        }
+        assert_eq!(parent, theme::load_default());
+        assert!(parent.colors.get("warning").is_none());
+
+        // The cached theme follows the parent theme and the overrides.
+        let mut other = parent.clone();
+        other.shadow = !parent.shadow;
+        view.set_border_style(BorderStyle::Rounded);
+        let theme = view.theme(&other);
+        assert_eq!(theme.colors.get("view"), Some(red));
+        assert_eq!(theme.borders, BorderStyle::Rounded);
+        assert_eq!(theme.shadow, other.shadow);
+    }
+}